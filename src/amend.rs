@@ -0,0 +1,711 @@
+use std::borrow::Cow;
+
+use crate::{
+    attributes::Attribute,
+    designation::Designation,
+    edit::Arena,
+    reference::UslmRef,
+    span::Span,
+    tags::{Core, Tag, TagType, HIERARCHY},
+    validate::Diagnostic,
+    Uslm,
+};
+
+/// One `instruction` of a bill: what it changes and how.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Amendment<'a, 's> {
+    /// The provision the instruction amends, from its own `ref` or the
+    /// closest enclosing instruction's.
+    pub target: Option<UslmRef<'s>>,
+    pub action: AmendmentAction<'a, 's>,
+    /// The `instruction` element in the bill.
+    pub span: Span,
+}
+
+/// A change to a provision, read from the wording of the `action`
+/// elements of an instruction and the quoted text and content following
+/// them.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AmendmentAction<'a, 's> {
+    /// "by striking "old"", optionally "each place it appears".
+    Strike { text: &'a str, everywhere: bool },
+    /// "by inserting "new" after "anchor"" and the like.
+    Insert { text: &'a str, anchor: Anchor<'a> },
+    /// "by striking "old" and inserting "new"".
+    StrikeAndInsert {
+        old: &'a str,
+        new: &'a str,
+        everywhere: bool,
+    },
+    /// "by redesignating "c" as "d"", for the provision of the target
+    /// designated "c".
+    Redesignate { from: &'a str, to: &'a str },
+    /// "is repealed", or striking the whole provision, the target or the
+    /// one named by a `ref` after the action.
+    Repeal,
+    /// "by adding at the end the following", with the `quotedContent`.
+    AddAtEnd(&'a Tag<'s>),
+    /// "by inserting before subsection (a) the following", with the
+    /// designation of the provision of the target it names, if any, and
+    /// the `quotedContent`. Without a designation the content goes before
+    /// the target itself.
+    InsertBefore {
+        anchor: Option<Designation>,
+        content: &'a Tag<'s>,
+    },
+    /// "by inserting after subsection (a) the following", as
+    /// [`AmendmentAction::InsertBefore`].
+    InsertAfter {
+        anchor: Option<Designation>,
+        content: &'a Tag<'s>,
+    },
+    /// Wording not covered by the variants above, lowercased.
+    Unrecognized(String),
+}
+
+/// Where inserted text goes within a provision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor<'a> {
+    Before(&'a str),
+    After(&'a str),
+    End,
+}
+
+/// The outcome of applying an amending bill to a document.
+#[derive(Debug)]
+pub struct Amended<'s> {
+    pub document: Uslm<'s>,
+    /// One diagnostic per instruction that could not be applied, with the
+    /// span of the instruction in the bill.
+    pub unapplied: Vec<Diagnostic>,
+}
+
+impl<'s> Uslm<'s> {
+    /// The amendments made by the `instruction` elements of this document,
+    /// in document order. Instructions inside `quotedContent` belong to
+    /// the quoted text and are left out.
+    pub fn amendments(&self) -> Vec<Amendment<'_, 's>> {
+        let mut instructions = Vec::new();
+        for tag in &self.content {
+            collect_instructions(tag, None, &mut instructions);
+        }
+        instructions
+            .into_iter()
+            .map(|(instruction, target)| {
+                let (action, struck) = action(instruction);
+                Amendment {
+                    target: struck.or(target).and_then(UslmRef::parse),
+                    action,
+                    span: instruction.span(),
+                }
+            })
+            .collect()
+    }
+
+    /// Applies the amendments of `bill` to a copy of this document, in the
+    /// order they appear in the bill. Identifiers changed by
+    /// redesignations are stored in `arena`.
+    pub fn amended_by<'a>(&self, arena: &'a Arena, bill: &'a Uslm<'s>) -> Amended<'a> {
+        let mut document: Uslm<'a> = self.clone();
+        let mut unapplied = Vec::new();
+        for amendment in bill.amendments() {
+            let result = match &amendment.target {
+                Some(target) => apply(&mut document, arena, target.href, amendment.action),
+                None => Err("the instruction does not reference a target".to_string()),
+            };
+            if let Err(reason) = result {
+                unapplied.push(Diagnostic::error("amendment", reason, amendment.span));
+            }
+        }
+        Amended {
+            document,
+            unapplied,
+        }
+    }
+}
+
+/// Finds every instruction carrying actions, paired with the `href` of
+/// its own `ref` or, failing that, of the closest enclosing instruction.
+fn collect_instructions<'a, 's>(
+    tag: &'a Tag<'s>,
+    target: Option<&'s str>,
+    instructions: &mut Vec<(&'a Tag<'s>, Option<&'s str>)>,
+) {
+    let mut target = target;
+    match tag.tag_type {
+        TagType::Core(Core::QuotedContent) => return,
+        TagType::Core(Core::Instruction) => {
+            let reference = tag
+                .children
+                .iter()
+                .filter(|child| child.tag_type == TagType::Core(Core::Ref))
+                .find_map(Tag::href);
+            target = reference.or(target);
+            if tag
+                .children
+                .iter()
+                .any(|child| child.tag_type == TagType::Core(Core::Action))
+            {
+                instructions.push((tag, target));
+            }
+        }
+        _ => {}
+    }
+    for child in &tag.children {
+        collect_instructions(child, target, instructions);
+    }
+}
+
+fn has_identifier(tag: &Tag, identifier: &str) -> bool {
+    tag.attributes
+        .iter()
+        .any(|attribute| matches!(attribute, Attribute::Identifier(value) if *value == identifier))
+}
+
+enum Item<'a, 's> {
+    Action(String),
+    Text(&'a str),
+    Content(&'a Tag<'s>),
+}
+
+/// The action of `instruction`, and the `href` of the provision it
+/// strikes when that is named by a `ref` after the action rather than by
+/// the target of the instruction.
+fn action<'a, 's>(instruction: &'a Tag<'s>) -> (AmendmentAction<'a, 's>, Option<&'s str>) {
+    let items: Vec<Item> = instruction
+        .children
+        .iter()
+        .filter_map(|child| match child.tag_type {
+            TagType::Core(Core::Action) => Some(Item::Action(child.text().to_lowercase())),
+            TagType::Core(Core::QuotedText) => {
+                Some(Item::Text(child.content.as_deref().unwrap_or_default()))
+            }
+            TagType::Core(Core::QuotedContent) => Some(Item::Content(child)),
+            _ => None,
+        })
+        .collect();
+    let everywhere = items
+        .iter()
+        .any(|item| matches!(item, Item::Action(action) if action.contains("each place")));
+
+    let action = match items.as_slice() {
+        [Item::Action(strike), Item::Text(old), Item::Action(insert), Item::Text(new), ..]
+            if strike.contains("striking") && insert.contains("inserting") =>
+        {
+            AmendmentAction::StrikeAndInsert {
+                old,
+                new,
+                everywhere,
+            }
+        }
+        [Item::Action(insert), Item::Text(text), Item::Action(position), Item::Text(anchor), ..]
+            if insert.contains("inserting")
+                && (position.contains("after") || position.contains("before")) =>
+        {
+            let anchor = match position.contains("after") {
+                true => Anchor::After(anchor),
+                false => Anchor::Before(anchor),
+            };
+            AmendmentAction::Insert { text, anchor }
+        }
+        [Item::Action(redesignate), Item::Text(from), Item::Action(_), Item::Text(to), ..]
+            if redesignate.contains("redesignating") =>
+        {
+            AmendmentAction::Redesignate { from, to }
+        }
+        [Item::Action(strike), Item::Text(text), ..] if strike.contains("striking") => {
+            AmendmentAction::Strike { text, everywhere }
+        }
+        [Item::Action(insert), Item::Text(text), ..]
+            if (insert.contains("inserting") || insert.contains("adding"))
+                && insert.contains("at the end") =>
+        {
+            AmendmentAction::Insert {
+                text,
+                anchor: Anchor::End,
+            }
+        }
+        [Item::Action(action), Item::Content(content), ..] if action.contains("at the end") => {
+            AmendmentAction::AddAtEnd(content)
+        }
+        [Item::Action(action), Item::Content(content), ..]
+            if action.contains("inserting after") =>
+        {
+            AmendmentAction::InsertAfter {
+                anchor: anchor_provision(instruction, "inserting after"),
+                content,
+            }
+        }
+        [Item::Action(action), Item::Content(content), ..]
+            if action.contains("inserting before") =>
+        {
+            AmendmentAction::InsertBefore {
+                anchor: anchor_provision(instruction, "inserting before"),
+                content,
+            }
+        }
+        [Item::Action(action)] if action.contains("repeal") => AmendmentAction::Repeal,
+        [Item::Action(action)] if action.contains("striking") => match struck(instruction) {
+            Some(href) => return (AmendmentAction::Repeal, href),
+            None => unrecognized(&items),
+        },
+        _ => unrecognized(&items),
+    };
+    (action, None)
+}
+
+fn unrecognized<'a, 's>(items: &[Item]) -> AmendmentAction<'a, 's> {
+    AmendmentAction::Unrecognized(
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Action(action) => Some(action.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// What a lone "striking" action strikes: the whole target when nothing
+/// follows it, as `Some(None)`, or the provision of the `ref` right after
+/// it, as in "by striking subsection (b)". `None` for anything else.
+fn struck<'s>(instruction: &Tag<'s>) -> Option<Option<&'s str>> {
+    let at = instruction
+        .children
+        .iter()
+        .rposition(|child| child.tag_type == TagType::Core(Core::Action))?;
+    match &instruction.children[at + 1..] {
+        [] => Some(None),
+        [reference] if reference.tag_type == TagType::Core(Core::Ref) => reference.href().map(Some),
+        _ => None,
+    }
+}
+
+/// The designation following `wording` in the first action using it, as
+/// "(a)" in "by inserting after subsection (a) the following". The text is
+/// read as written, since case tells "(A)" from "(a)".
+fn anchor_provision(instruction: &Tag, wording: &str) -> Option<Designation> {
+    let text = instruction
+        .children
+        .iter()
+        .filter(|child| child.tag_type == TagType::Core(Core::Action))
+        .map(Tag::text)
+        .find(|text| text.to_ascii_lowercase().contains(wording))?;
+    let at = text.to_ascii_lowercase().find(wording)? + wording.len();
+    let mut words = text[at..].split_whitespace();
+    let mut word = words.next()?;
+    if HIERARCHY
+        .iter()
+        .any(|level| level.name().eq_ignore_ascii_case(word))
+    {
+        word = words.next()?;
+    }
+    let word = match word.strip_prefix('(') {
+        Some(rest) => rest.split(')').next()?,
+        None => word.trim_end_matches([',', '.', ':', ';']),
+    };
+    if word.eq_ignore_ascii_case("the") {
+        return None;
+    }
+    word.parse().ok()
+}
+
+/// The position among the children of `tag` of the level designated
+/// `designation`.
+fn provision(tag: &Tag, designation: &Designation) -> Option<usize> {
+    tag.children.iter().position(|child| {
+        matches!(child.tag_type, TagType::Level(_))
+            && child.designation().as_ref() == Some(designation)
+    })
+}
+
+fn apply<'a>(
+    document: &mut Uslm<'a>,
+    arena: &'a Arena,
+    target: &str,
+    action: AmendmentAction<'a, '_>,
+) -> Result<(), String> {
+    let (siblings, index) = siblings_mut(&mut document.content, target)
+        .ok_or_else(|| format!("`{target}` does not exist"))?;
+    let missing = |text: &str| format!("\"{text}\" does not appear in `{target}`");
+    match action {
+        AmendmentAction::Strike { text, everywhere } => {
+            if replace_text(&mut siblings[index], text, "", everywhere) == 0 {
+                return Err(missing(text));
+            }
+        }
+        AmendmentAction::StrikeAndInsert {
+            old,
+            new,
+            everywhere,
+        } => {
+            if replace_text(&mut siblings[index], old, new, everywhere) == 0 {
+                return Err(missing(old));
+            }
+        }
+        AmendmentAction::Insert { text, anchor } => {
+            let tag = &mut siblings[index];
+            let (anchor, replacement) = match anchor {
+                Anchor::Before(anchor) => (anchor, format!("{text}{anchor}")),
+                Anchor::After(anchor) => (anchor, format!("{anchor}{text}")),
+                Anchor::End => {
+                    let content = tag.content.get_or_insert_with(Default::default);
+                    *content = Cow::Owned(format!("{content}{text}"));
+                    return Ok(());
+                }
+            };
+            if replace_text(tag, anchor, &replacement, false) == 0 {
+                return Err(missing(anchor));
+            }
+        }
+        AmendmentAction::Redesignate { from, to } => {
+            let child = from
+                .parse()
+                .ok()
+                .and_then(|from| provision(&siblings[index], &from))
+                .ok_or_else(|| format!("`{target}` has no provision \"{from}\""))?;
+            let identifier = siblings[index].children[child]
+                .identifier()
+                .ok_or_else(|| format!("provision \"{from}\" of `{target}` has no identifier"))?;
+            document.redesignate(arena, identifier, to.trim_matches(['(', ')']))?;
+        }
+        AmendmentAction::Repeal => {
+            siblings.remove(index);
+        }
+        AmendmentAction::AddAtEnd(content) => {
+            siblings[index]
+                .children
+                .extend(content.children.iter().cloned());
+        }
+        AmendmentAction::InsertBefore { anchor, content } => {
+            let (siblings, index) = anchored(siblings, index, target, anchor)?;
+            siblings.splice(index..index, content.children.iter().cloned());
+        }
+        AmendmentAction::InsertAfter { anchor, content } => {
+            let (siblings, index) = anchored(siblings, index, target, anchor)?;
+            siblings.splice(index + 1..index + 1, content.children.iter().cloned());
+        }
+        AmendmentAction::Unrecognized(wording) => {
+            return Err(format!("the action \"{wording}\" is not recognized"));
+        }
+    }
+    Ok(())
+}
+
+/// The tags holding the provision of the target designated `anchor`, and
+/// its index among them, or the target itself without an anchor.
+fn anchored<'a, 's>(
+    siblings: &'a mut Vec<Tag<'s>>,
+    index: usize,
+    target: &str,
+    anchor: Option<Designation>,
+) -> Result<(&'a mut Vec<Tag<'s>>, usize), String> {
+    let Some(anchor) = anchor else {
+        return Ok((siblings, index));
+    };
+    let tag = &mut siblings[index];
+    let child = provision(tag, &anchor)
+        .ok_or_else(|| format!("`{target}` has no provision \"{anchor}\""))?;
+    Ok((&mut tag.children, child))
+}
+
+/// The tags holding the tag with `identifier`, and its index among them.
+pub(crate) fn siblings_mut<'a, 's>(
+    tags: &'a mut Vec<Tag<'s>>,
+    identifier: &str,
+) -> Option<(&'a mut Vec<Tag<'s>>, usize)> {
+    if let Some(index) = tags.iter().position(|tag| has_identifier(tag, identifier)) {
+        return Some((tags, index));
+    }
+    tags.iter_mut()
+        .find_map(|tag| siblings_mut(&mut tag.children, identifier))
+}
+
+/// Replaces the first occurrence of `old` in the text of `tag`, or every
+/// occurrence when `everywhere` is set, returning how many were replaced.
+fn replace_text(tag: &mut Tag, old: &str, new: &str, everywhere: bool) -> usize {
+    let mut replaced = 0;
+    for child in &mut tag.children {
+        replaced += replace_text(child, old, new, everywhere);
+        if replaced > 0 && !everywhere {
+            return replaced;
+        }
+    }
+    if let Some(content) = &mut tag.content {
+        let count = content.matches(old).count();
+        if count > 0 && !old.is_empty() {
+            let (replacement, count) = match everywhere {
+                true => (content.replace(old, new), count),
+                false => (content.replacen(old, new, 1), 1),
+            };
+            *content = Cow::Owned(replacement);
+            replaced += count;
+        }
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const TARGET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5">
+<main>
+<section identifier="/us/usc/t5/s552">
+<num value="552">Sec. 552.</num>
+<heading>Public information</heading>
+<subsection identifier="/us/usc/t5/s552/a">
+<num value="a">a.</num>
+<content>Each agency shall respond within five days and publish within five days</content>
+</subsection>
+<subsection identifier="/us/usc/t5/s552/b">
+<num value="b">b.</num>
+<content>This section does not apply to national defense</content>
+</subsection>
+</section>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_strike_and_insert() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let mut bill = r#"<bill><main><section>
+<instruction><ref href="/us/usc/t5/s552/a">Subsection a</ref><action>is amended by striking</action><quotedText>five days</quotedText><action>and inserting</action><quotedText>ten business days</quotedText></instruction>
+</section></main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let arena = Arena::new();
+        let output = target.amended_by(&arena, &bill);
+
+        assert_eq!(output.unapplied, vec![]);
+        assert_eq!(
+            output.document.content[0].children[0].children[0].children[2].children[1].content,
+            Some(Cow::Borrowed(
+                "Each agency shall respond within ten business days and publish within five days"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_strike_each_place() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let mut bill = r#"<bill><main>
+<instruction><ref href="/us/usc/t5/s552/a">Subsection a</ref><action>is amended by striking</action><quotedText>five</quotedText><action>each place it appears and inserting</action><quotedText>ten</quotedText></instruction>
+</main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let arena = Arena::new();
+        let output = target.amended_by(&arena, &bill);
+
+        assert_eq!(
+            output.document.content[0].children[0].children[0].children[2].children[1].text(),
+            "Each agency shall respond within ten days and publish within ten days"
+        );
+    }
+
+    #[test]
+    fn test_add_at_end_and_repeal() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let mut bill = r#"<bill><main>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by adding at the end the following</action><quotedContent><subsection identifier="/us/usc/t5/s552/c"><num value="c">c.</num><content>Records shall be kept</content></subsection></quotedContent></instruction>
+<instruction><ref href="/us/usc/t5/s552/b">Subsection b</ref><action>is repealed</action></instruction>
+</main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let arena = Arena::new();
+        let output = target.amended_by(&arena, &bill);
+
+        let section = &output.document.content[0].children[0].children[0];
+        let numbers: Vec<String> = section.children.iter().map(|child| child.text()).collect();
+        assert_eq!(output.unapplied, vec![]);
+        assert_eq!(
+            numbers,
+            vec![
+                "Sec. 552.",
+                "Public information",
+                "a. Each agency shall respond within five days and publish within five days",
+                "c. Records shall be kept",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unapplied_instructions() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let input = r#"<bill><main><instruction><ref href="/us/usc/t5/s553">Section 553</ref><action>is repealed</action></instruction><instruction><ref href="/us/usc/t5/s552/b">Subsection b</ref><action>is amended by striking</action><quotedText>intelligence</quotedText></instruction></main></bill>"#;
+        let mut bill = input;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let arena = Arena::new();
+        let output = target.amended_by(&arena, &bill);
+
+        let second = input.rfind("<instruction>").unwrap();
+        assert_eq!(output.document, target);
+        assert_eq!(
+            output.unapplied,
+            vec![
+                Diagnostic::error(
+                    "amendment",
+                    "`/us/usc/t5/s553` does not exist",
+                    bill.content[0].children[0].children[0].span()
+                ),
+                Diagnostic::error(
+                    "amendment",
+                    "\"intelligence\" does not appear in `/us/usc/t5/s552/b`",
+                    crate::Span::new(second, input.len() - "</main></bill>".len())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_amendments() {
+        let mut bill = r#"<bill><main><section>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended</action>
+<instruction><ref href="/us/usc/t5/s552/a">in subsection a</ref><action>by striking</action><quotedText>five</quotedText><action>each place it appears</action></instruction>
+<instruction><action>by redesignating subsection</action><quotedText>b</quotedText><action>as subsection</action><quotedText>c</quotedText></instruction>
+<instruction><action>by inserting after subsection a the following</action><quotedContent><subsection><num value="b">b.</num></subsection></quotedContent></instruction>
+</instruction>
+</section></main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let output: Vec<_> = bill
+            .amendments()
+            .into_iter()
+            .map(|amendment| (amendment.target.map(|target| target.href), amendment.action))
+            .collect();
+
+        let quoted = &bill.content[0].children[0].children[0].children[0].children[4].children[1];
+        assert_eq!(
+            output,
+            vec![
+                (
+                    Some("/us/usc/t5/s552"),
+                    AmendmentAction::Unrecognized("is amended".to_string())
+                ),
+                (
+                    Some("/us/usc/t5/s552/a"),
+                    AmendmentAction::Strike {
+                        text: "five",
+                        everywhere: true
+                    }
+                ),
+                (
+                    Some("/us/usc/t5/s552"),
+                    AmendmentAction::Redesignate { from: "b", to: "c" }
+                ),
+                (
+                    Some("/us/usc/t5/s552"),
+                    AmendmentAction::InsertAfter {
+                        anchor: Some("a".parse().unwrap()),
+                        content: quoted
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strike_provision() {
+        let mut bill = r#"<bill><main>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by striking</action><ref href="/us/usc/t5/s552/b">subsection (b)</ref></instruction>
+<instruction><ref href="/us/usc/t5/s553">Section 553</ref><action>is amended by striking</action><ref href="/us/usc/t5/s553/a">subsection (a)</ref><ref href="/us/usc/t5/s553/b">subsection (b)</ref></instruction>
+<instruction><ref href="/us/usc/t5/s554">Section 554</ref><action>is amended by striking it</action></instruction>
+</main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let output: Vec<_> = bill
+            .amendments()
+            .into_iter()
+            .map(|amendment| (amendment.target.map(|target| target.href), amendment.action))
+            .collect();
+
+        assert_eq!(
+            output,
+            vec![
+                (Some("/us/usc/t5/s552/b"), AmendmentAction::Repeal),
+                (
+                    Some("/us/usc/t5/s553"),
+                    AmendmentAction::Unrecognized("is amended by striking".to_string())
+                ),
+                (Some("/us/usc/t5/s554"), AmendmentAction::Repeal),
+            ]
+        );
+
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let arena = Arena::new();
+        let amended = target.amended_by(&arena, &bill);
+        let section = &amended.document.content[0].children[0].children[0];
+        assert_eq!(section.identifier(), Some("/us/usc/t5/s552"));
+        assert_eq!(
+            section
+                .children
+                .iter()
+                .filter_map(Tag::identifier)
+                .collect::<Vec<_>>(),
+            vec!["/us/usc/t5/s552/a"]
+        );
+    }
+
+    #[test]
+    fn test_redesignate() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let mut bill = r#"<bill><main>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by redesignating subsection</action><quotedText>b</quotedText><action>as subsection</action><quotedText>c</quotedText></instruction>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by redesignating subsection</action><quotedText>f</quotedText><action>as subsection</action><quotedText>g</quotedText></instruction>
+</main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+        let arena = Arena::new();
+
+        let output = target.amended_by(&arena, &bill);
+
+        let section = &output.document.content[0].children[0].children[0];
+        assert_eq!(section.children[0].text(), "Sec. 552.");
+        assert_eq!(section.children[3].identifier(), Some("/us/usc/t5/s552/c"));
+        let num = &section.children[3].children[0];
+        assert_eq!(num.attributes, vec![Attribute::Value("c")]);
+        assert_eq!(num.text(), "c.");
+        assert_eq!(
+            output.unapplied,
+            vec![Diagnostic::error(
+                "amendment",
+                "`/us/usc/t5/s552` has no provision \"f\"",
+                bill.amendments()[1].span
+            )]
+        );
+    }
+
+    #[test]
+    fn test_insert_after_provision() {
+        let mut target = TARGET;
+        let target = Uslm::parse(&mut target).unwrap();
+        let mut bill = r#"<bill><main>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by inserting after subsection (a) the following</action><quotedContent><subsection><num value="a-1">a-1.</num></subsection></quotedContent></instruction>
+<instruction><ref href="/us/usc/t5/s552">Section 552</ref><action>is amended by inserting before subsection (a) the following</action><quotedContent><subsection><num value="a-0">a-0.</num></subsection></quotedContent></instruction>
+</main></bill>"#;
+        let bill = Uslm::parse(&mut bill).unwrap();
+
+        let arena = Arena::new();
+        let output = target.amended_by(&arena, &bill);
+
+        let section = &output.document.content[0].children[0].children[0];
+        let numbers: Vec<String> = section
+            .children
+            .iter()
+            .map(|child| child.children.first().map(Tag::text).unwrap_or_default())
+            .collect();
+        assert_eq!(output.unapplied, vec![]);
+        assert_eq!(output.document.content[0].children[0].children.len(), 1);
+        assert_eq!(numbers, vec!["", "", "a-0.", "a.", "a-1.", "b."]);
+    }
+}
@@ -1,9 +1,11 @@
-use std::{error::Error, str::FromStr};
+use std::{error::Error, fmt, str::FromStr};
 
 use mime::Mime;
 use url::Url;
 
-#[derive(Debug, PartialEq, Eq)]
+use crate::temporal::Date;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Attribute<'s> {
     Version(Version),
     Encoding(Encoding),
@@ -18,15 +20,17 @@ pub enum Attribute<'s> {
     XmlnsHtml(Url),
     XmlnsiUslm(Url),
     XmlnsiXsi(Url),
-    XsiSchemaLocation(Url),
+    /// Pairs of a namespace and the location of its schema.
+    XsiSchemaLocation(Typed<'s, Vec<(Url, Url)>>),
     XmlLang(&'s str),
     Id(&'s str),
     Role(&'s str),
     Value(&'s str),
     StartValue(&'s str),
     EndValue(&'s str),
-    Display(&'s str),
-    Class(&'s str),
+    Display(Typed<'s, bool>),
+    /// The space-separated class names.
+    Class(Typed<'s, Vec<&'s str>>),
     Identifier(&'s str),
     SenateId(&'s str),
     Leaders(&'s str),
@@ -35,7 +39,7 @@ pub enum Attribute<'s> {
     TemporalId(&'s str),
     Pos(&'s str),
     PosText(&'s str),
-    PosCount(&'s str),
+    PosCount(Typed<'s, u32>),
     Idref(&'s str),
     Src(&'s str),
     Note(&'s str),
@@ -48,22 +52,24 @@ pub enum Attribute<'s> {
     SortOrder(&'s str),
     Portion(&'s str),
     Occurrence(&'s str),
-    CommencementDate(&'s str),
-    Date(&'s str),
-    BeginDate(&'s str),
-    EndDate(&'s str),
+    CommencementDate(Typed<'s, Date>),
+    Date(Typed<'s, Date>),
+    BeginDate(Typed<'s, Date>),
+    EndDate(Typed<'s, Date>),
     StartPeriod(&'s str),
     EndPeriod(&'s str),
-    Partial(&'s str),
-    ColSpan(&'s str),
-    RowSpan(&'s str),
+    Partial(Typed<'s, bool>),
+    ColSpan(Typed<'s, u32>),
+    RowSpan(Typed<'s, u32>),
     Topic(&'s str),
-    Orientation(&'s str),
+    Orientation(Typed<'s, Orientation>),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The XML version of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Version {
     One,
+    OnePointOne,
 }
 
 impl FromStr for Version {
@@ -72,94 +78,587 @@ impl FromStr for Version {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "1.0" => Ok(Version::One),
-            _ => panic!("Unrecognized xml version"),
+            "1.1" => Ok(Version::OnePointOne),
+            _ => Err(format!("unsupported XML version `{s}`").into()),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The character encoding named by an XML declaration. Names compare
+/// without regard to case, as `utf-8` and `UTF-8`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Encoding {
     Utf8,
+    Utf16,
+    Latin1,
+    Ascii,
+    /// Any other registered encoding name.
+    Other(String),
 }
 
 impl FromStr for Encoding {
     type Err = Box<dyn Error>;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = s.starts_with(|c: char| c.is_ascii_alphabetic())
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+        if !valid {
+            return Err(format!("invalid encoding name `{s}`").into());
+        }
+        let encoding = match s.to_ascii_uppercase().as_str() {
+            "UTF-8" => Encoding::Utf8,
+            "UTF-16" => Encoding::Utf16,
+            "ISO-8859-1" | "LATIN1" => Encoding::Latin1,
+            "US-ASCII" | "ASCII" => Encoding::Ascii,
+            _ => Encoding::Other(s.to_string()),
+        };
+        Ok(encoding)
+    }
+}
+
+/// An attribute value parsed into a `T`, along with the text it was
+/// parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Typed<'s, T> {
+    raw: &'s str,
+    value: T,
+}
+
+impl<'s, T> Typed<'s, T> {
+    pub fn new(raw: &'s str, value: T) -> Self {
+        Typed { raw, value }
+    }
+
+    /// The value as written in the document.
+    pub fn raw(&self) -> &'s str {
+        self.raw
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'s, T: FromStr> Typed<'s, T>
+where
+    T::Err: fmt::Display,
+{
+    pub fn parse(raw: &'s str) -> Result<Self, String> {
+        raw.parse()
+            .map(|value| Typed { raw, value })
+            .map_err(|error| format!("invalid value `{raw}`: {error}"))
+    }
+}
+
+impl<T> fmt::Display for Typed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.raw)
+    }
+}
+
+/// The `orientation` of a table or page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+impl FromStr for Orientation {
+    type Err = String;
+
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "UTF-8" => Ok(Encoding::Utf8),
-            _ => panic!("Unrecognized Encoding"),
+            "portrait" => Ok(Orientation::Portrait),
+            "landscape" => Ok(Orientation::Landscape),
+            _ => Err("expected `portrait` or `landscape`".to_string()),
         }
     }
 }
 
-pub(super) trait VecExt<'s> {
-    fn into_attributes(self) -> Vec<Attribute<'s>>;
-}
-
-impl<'s> VecExt<'s> for Vec<(&'s str, &'s str)> {
-    fn into_attributes(self) -> Vec<Attribute<'s>> {
-        self.into_iter()
-            .flat_map(|(k, v)| -> Result<Attribute<'_>, Box<dyn Error>> {
-                let attribute = match k {
-                    "version" => Attribute::Version(Version::from_str(v)?),
-                    "encoding" => Attribute::Encoding(Encoding::from_str(v)?),
-                    "name" => Attribute::Name(v),
-                    "type" => Attribute::Type(Mime::from_str(v).unwrap()),
-                    "style" => Attribute::Style(v),
-                    "styleType" => Attribute::StyleType(v),
-                    "href" => Attribute::Href(v),
-                    "xmlns" => Attribute::Xmlns(Url::from_str(v)?),
-                    "xml:base" => Attribute::XmlBase(Url::from_str(v)?),
-                    "xmlns:dc" => Attribute::XmlnsDc(Url::from_str(v)?),
-                    "xmlns:html" => Attribute::XmlnsHtml(Url::from_str(v)?),
-                    "xmlns:uslm" => Attribute::XmlnsiUslm(Url::from_str(v)?),
-                    "xmlns:xsi" => Attribute::XmlnsiXsi(Url::from_str(v)?),
-                    "xsi:schemaLocation" => Attribute::XsiSchemaLocation(Url::from_str(v)?),
-                    "xml:lang" => Attribute::XmlLang(v),
-                    "id" => Attribute::Id(v),
-                    "role" => Attribute::Role(v),
-                    "value" => Attribute::Value(v),
-                    "startValue" => Attribute::StartValue(v),
-                    "endValue" => Attribute::EndValue(v),
-                    "display" => Attribute::Display(v),
-                    "class" => Attribute::Class(v),
-                    "identifier" => Attribute::Identifier(v),
-                    "senateId" => Attribute::SenateId(v),
-                    "leaders" => Attribute::Leaders(v),
-                    "title" => Attribute::Title(v),
-                    "status" => Attribute::Status(v),
-                    "temporalId" => Attribute::TemporalId(v),
-                    "pos" => Attribute::Pos(v),
-                    "posText" => Attribute::PosText(v),
-                    "posCount" => Attribute::PosCount(v),
-                    "idref" => Attribute::Idref(v),
-                    "src" => Attribute::Src(v),
-                    "note" => Attribute::Note(v),
-                    "alt" => Attribute::Alt(v),
-                    "meta" => Attribute::Meta(v),
-                    "misc" => Attribute::Misc(v),
-                    "draftingTip" => Attribute::DraftingTip(v),
-                    "codificationTip" => Attribute::CodificationTip(v),
-                    "brief" => Attribute::Brief(v),
-                    "sortOrder" => Attribute::SortOrder(v),
-                    "portion" => Attribute::Portion(v),
-                    "occurrence" => Attribute::Occurrence(v),
-                    "commencementDate" => Attribute::CommencementDate(v),
-                    "date" => Attribute::Date(v),
-                    "beginDate" => Attribute::BeginDate(v),
-                    "endDate" => Attribute::EndDate(v),
-                    "startPeriod" => Attribute::StartPeriod(v),
-                    "endPeriod" => Attribute::EndPeriod(v),
-                    "partial" => Attribute::Partial(v),
-                    "colspan" => Attribute::ColSpan(v),
-                    "rowspan" => Attribute::RowSpan(v),
-                    "topic" => Attribute::Topic(v),
-                    "orientation" => Attribute::Orientation(v),
-                    _ => panic!("Unrecognized doc attribute variant: {:#?}", v),
-                };
-                Ok(attribute)
-            })
-            .collect()
+/// An `xs:boolean`, written `true`, `false`, `1` or `0`.
+fn boolean(value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!("invalid value `{value}`: expected a boolean")),
+    }
+}
+
+/// The namespace and schema location pairs of an `xsi:schemaLocation`.
+fn schema_locations(value: &str) -> Result<Vec<(Url, Url)>, String> {
+    let urls = value
+        .split_whitespace()
+        .map(Url::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("invalid value `{value}`: {error}"))?;
+    if urls.is_empty() || urls.len() % 2 != 0 {
+        return Err(format!(
+            "invalid value `{value}`: expected pairs of a namespace and a location"
+        ));
+    }
+    Ok(urls
+        .chunks(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect())
+}
+
+impl<'s> TryFrom<(&'s str, &'s str)> for Attribute<'s> {
+    type Error = Box<dyn Error>;
+
+    fn try_from((k, v): (&'s str, &'s str)) -> Result<Self, Self::Error> {
+        let attribute = match k {
+            "version" => Attribute::Version(Version::from_str(v)?),
+            "encoding" => Attribute::Encoding(Encoding::from_str(v)?),
+            "name" => Attribute::Name(v),
+            "type" => Attribute::Type(Mime::from_str(v)?),
+            "style" => Attribute::Style(v),
+            "styleType" => Attribute::StyleType(v),
+            "href" => Attribute::Href(v),
+            "xmlns" => Attribute::Xmlns(Url::from_str(v)?),
+            "xml:base" => Attribute::XmlBase(Url::from_str(v)?),
+            "xmlns:dc" => Attribute::XmlnsDc(Url::from_str(v)?),
+            "xmlns:html" => Attribute::XmlnsHtml(Url::from_str(v)?),
+            "xmlns:uslm" => Attribute::XmlnsiUslm(Url::from_str(v)?),
+            "xmlns:xsi" => Attribute::XmlnsiXsi(Url::from_str(v)?),
+            "xsi:schemaLocation" => {
+                Attribute::XsiSchemaLocation(Typed::new(v, schema_locations(v)?))
+            }
+            "xml:lang" => Attribute::XmlLang(v),
+            "id" => Attribute::Id(v),
+            "role" => Attribute::Role(v),
+            "value" => Attribute::Value(v),
+            "startValue" => Attribute::StartValue(v),
+            "endValue" => Attribute::EndValue(v),
+            "display" => Attribute::Display(Typed::new(v, boolean(v)?)),
+            "class" => Attribute::Class(Typed::new(v, v.split_whitespace().collect())),
+            "identifier" => Attribute::Identifier(v),
+            "senateId" => Attribute::SenateId(v),
+            "leaders" => Attribute::Leaders(v),
+            "title" => Attribute::Title(v),
+            "status" => Attribute::Status(v),
+            "temporalId" => Attribute::TemporalId(v),
+            "pos" => Attribute::Pos(v),
+            "posText" => Attribute::PosText(v),
+            "posCount" => Attribute::PosCount(Typed::parse(v)?),
+            "idref" => Attribute::Idref(v),
+            "src" => Attribute::Src(v),
+            "note" => Attribute::Note(v),
+            "alt" => Attribute::Alt(v),
+            "meta" => Attribute::Meta(v),
+            "misc" => Attribute::Misc(v),
+            "draftingTip" => Attribute::DraftingTip(v),
+            "codificationTip" => Attribute::CodificationTip(v),
+            "brief" => Attribute::Brief(v),
+            "sortOrder" => Attribute::SortOrder(v),
+            "portion" => Attribute::Portion(v),
+            "occurrence" => Attribute::Occurrence(v),
+            "commencementDate" => Attribute::CommencementDate(Typed::parse(v)?),
+            "date" => Attribute::Date(Typed::parse(v)?),
+            "beginDate" => Attribute::BeginDate(Typed::parse(v)?),
+            "endDate" => Attribute::EndDate(Typed::parse(v)?),
+            "startPeriod" => Attribute::StartPeriod(v),
+            "endPeriod" => Attribute::EndPeriod(v),
+            "partial" => Attribute::Partial(Typed::new(v, boolean(v)?)),
+            "colspan" => Attribute::ColSpan(Typed::parse(v)?),
+            "rowspan" => Attribute::RowSpan(Typed::parse(v)?),
+            "topic" => Attribute::Topic(v),
+            "orientation" => Attribute::Orientation(Typed::parse(v)?),
+            _ => return Err(format!("unrecognized attribute `{k}`").into()),
+        };
+        Ok(attribute)
+    }
+}
+
+/// Generates a marker type in [`kind`] for each variant of [`Attribute`],
+/// along with the index [`Attributes`] looks the variants up by.
+macro_rules! attribute_kinds {
+    ($($variant:ident = $name:literal: $value:ty,)*) => {
+        /// Marker types naming the kinds of [`Attribute`], as in
+        /// `tag.attr::<kind::Identifier>()`.
+        pub mod kind {
+            $(
+                #[doc = concat!("The `", $name, "` attribute.")]
+                #[derive(Debug, Clone, Copy)]
+                pub struct $variant;
+            )*
+        }
+
+        #[allow(clippy::enum_variant_names)]
+        enum Index {
+            $($variant,)*
+        }
+
+        /// The number of kinds of attributes.
+        const KINDS: usize = [$(Index::$variant),*].len();
+
+        $(
+            impl<'s> AttributeKind<'s> for kind::$variant {
+                type Value = $value;
+                const INDEX: usize = Index::$variant as usize;
+
+                fn value<'a>(attribute: &'a Attribute<'s>) -> Option<&'a Self::Value> {
+                    match attribute {
+                        Attribute::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+
+                fn value_mut<'a>(attribute: &'a mut Attribute<'s>) -> Option<&'a mut Self::Value> {
+                    match attribute {
+                        Attribute::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+
+        impl Attribute<'_> {
+            /// The name of the attribute in the markup, as in `xml:lang`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Attribute::$variant(_) => $name,)*
+                }
+            }
+
+            fn index(&self) -> usize {
+                match self {
+                    $(Attribute::$variant(_) => Index::$variant as usize,)*
+                }
+            }
+        }
+    };
+}
+
+attribute_kinds! {
+    Version = "version": Version,
+    Encoding = "encoding": Encoding,
+    Name = "name": &'s str,
+    Type = "type": Mime,
+    Style = "style": &'s str,
+    StyleType = "styleType": &'s str,
+    Href = "href": &'s str,
+    Xmlns = "xmlns": Url,
+    XmlBase = "xml:base": Url,
+    XmlnsDc = "xmlns:dc": Url,
+    XmlnsHtml = "xmlns:html": Url,
+    XmlnsiUslm = "xmlns:uslm": Url,
+    XmlnsiXsi = "xmlns:xsi": Url,
+    XsiSchemaLocation = "xsi:schemaLocation": Typed<'s, Vec<(Url, Url)>>,
+    XmlLang = "xml:lang": &'s str,
+    Id = "id": &'s str,
+    Role = "role": &'s str,
+    Value = "value": &'s str,
+    StartValue = "startValue": &'s str,
+    EndValue = "endValue": &'s str,
+    Display = "display": Typed<'s, bool>,
+    Class = "class": Typed<'s, Vec<&'s str>>,
+    Identifier = "identifier": &'s str,
+    SenateId = "senateId": &'s str,
+    Leaders = "leaders": &'s str,
+    Title = "title": &'s str,
+    Status = "status": &'s str,
+    TemporalId = "temporalId": &'s str,
+    Pos = "pos": &'s str,
+    PosText = "posText": &'s str,
+    PosCount = "posCount": Typed<'s, u32>,
+    Idref = "idref": &'s str,
+    Src = "src": &'s str,
+    Note = "note": &'s str,
+    Alt = "alt": &'s str,
+    Meta = "meta": &'s str,
+    Misc = "misc": &'s str,
+    DraftingTip = "draftingTip": &'s str,
+    CodificationTip = "codificationTip": &'s str,
+    Brief = "brief": &'s str,
+    SortOrder = "sortOrder": &'s str,
+    Portion = "portion": &'s str,
+    Occurrence = "occurrence": &'s str,
+    CommencementDate = "commencementDate": Typed<'s, Date>,
+    Date = "date": Typed<'s, Date>,
+    BeginDate = "beginDate": Typed<'s, Date>,
+    EndDate = "endDate": Typed<'s, Date>,
+    StartPeriod = "startPeriod": &'s str,
+    EndPeriod = "endPeriod": &'s str,
+    Partial = "partial": Typed<'s, bool>,
+    ColSpan = "colspan": Typed<'s, u32>,
+    RowSpan = "rowspan": Typed<'s, u32>,
+    Topic = "topic": &'s str,
+    Orientation = "orientation": Typed<'s, Orientation>,
+}
+
+/// A kind of [`Attribute`], named by one of the marker types in [`kind`].
+pub trait AttributeKind<'s> {
+    /// What the variant of [`Attribute`] holds.
+    type Value;
+    #[doc(hidden)]
+    const INDEX: usize;
+
+    fn value<'a>(attribute: &'a Attribute<'s>) -> Option<&'a Self::Value>;
+
+    fn value_mut<'a>(attribute: &'a mut Attribute<'s>) -> Option<&'a mut Self::Value>;
+}
+
+/// The attributes of a tag in document order, indexed by kind so that
+/// looking one up takes constant time. Markup allows each attribute once
+/// per tag; should a kind repeat, lookups find the first.
+#[derive(Clone)]
+pub struct Attributes<'s> {
+    list: Vec<Attribute<'s>>,
+    /// The position in `list` of each kind, `ABSENT` when missing.
+    index: [u8; KINDS],
+}
+
+const ABSENT: u8 = u8::MAX;
+
+impl<'s> Attributes<'s> {
+    pub fn new() -> Self {
+        Attributes {
+            list: Vec::new(),
+            index: [ABSENT; KINDS],
+        }
+    }
+
+    pub fn get<A: AttributeKind<'s>>(&self) -> Option<&A::Value> {
+        self.position(A::INDEX)
+            .and_then(|position| A::value(&self.list[position]))
+    }
+
+    pub fn get_mut<A: AttributeKind<'s>>(&mut self) -> Option<&mut A::Value> {
+        self.position(A::INDEX)
+            .and_then(|position| A::value_mut(&mut self.list[position]))
+    }
+
+    /// Replaces the attribute of the same kind, returning it, or adds
+    /// `attribute` at the end.
+    pub fn set(&mut self, attribute: Attribute<'s>) -> Option<Attribute<'s>> {
+        match self.position(attribute.index()) {
+            Some(position) => Some(std::mem::replace(&mut self.list[position], attribute)),
+            None => {
+                self.push(attribute);
+                None
+            }
+        }
+    }
+
+    /// Removes the attribute of kind `A`, returning its position and the
+    /// attribute.
+    pub fn remove<A: AttributeKind<'s>>(&mut self) -> Option<(usize, Attribute<'s>)> {
+        let position = self.position(A::INDEX)?;
+        let attribute = self.list.remove(position);
+        self.reindex();
+        Some((position, attribute))
+    }
+
+    fn position(&self, index: usize) -> Option<usize> {
+        match self.index[index] {
+            // Kinds first seen past what `u8` holds are found by scanning.
+            ABSENT if self.list.len() > usize::from(ABSENT) => self
+                .list
+                .iter()
+                .position(|attribute| attribute.index() == index),
+            ABSENT => None,
+            position => Some(usize::from(position)),
+        }
+    }
+
+    fn push(&mut self, attribute: Attribute<'s>) {
+        let index = attribute.index();
+        if self.index[index] == ABSENT && self.list.len() < usize::from(ABSENT) {
+            self.index[index] = self.list.len() as u8;
+        }
+        self.list.push(attribute);
+    }
+
+    fn reindex(&mut self) {
+        let list = std::mem::take(&mut self.list);
+        self.index = [ABSENT; KINDS];
+        for attribute in list {
+            self.push(attribute);
+        }
+    }
+}
+
+impl Default for Attributes<'_> {
+    fn default() -> Self {
+        Attributes::new()
+    }
+}
+
+impl fmt::Debug for Attributes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.list).finish()
+    }
+}
+
+impl PartialEq for Attributes<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl Eq for Attributes<'_> {}
+
+impl<'s> PartialEq<Vec<Attribute<'s>>> for Attributes<'s> {
+    fn eq(&self, other: &Vec<Attribute<'s>>) -> bool {
+        &self.list == other
+    }
+}
+
+impl<'s> std::ops::Deref for Attributes<'s> {
+    type Target = [Attribute<'s>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+impl<'s> From<Vec<Attribute<'s>>> for Attributes<'s> {
+    fn from(list: Vec<Attribute<'s>>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<'s> FromIterator<Attribute<'s>> for Attributes<'s> {
+    fn from_iter<I: IntoIterator<Item = Attribute<'s>>>(iter: I) -> Self {
+        let mut attributes = Attributes::new();
+        attributes.extend(iter);
+        attributes
+    }
+}
+
+/// Appends the attributes in order, as parsed, without replacing those of
+/// the same kind; see [`Attributes::set`].
+impl<'s> Extend<Attribute<'s>> for Attributes<'s> {
+    fn extend<I: IntoIterator<Item = Attribute<'s>>>(&mut self, iter: I) {
+        for attribute in iter {
+            self.push(attribute);
+        }
+    }
+}
+
+impl<'a, 's> IntoIterator for &'a Attributes<'s> {
+    type Item = &'a Attribute<'s>;
+    type IntoIter = std::slice::Iter<'a, Attribute<'s>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+impl<'s> IntoIterator for Attributes<'s> {
+    type Item = Attribute<'s>;
+    type IntoIter = std::vec::IntoIter<Attribute<'s>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_typed_attributes() {
+        let attribute = |key, value| Attribute::try_from((key, value)).unwrap();
+
+        assert_eq!(
+            attribute("colspan", "2"),
+            Attribute::ColSpan(Typed::new("2", 2))
+        );
+        assert_eq!(
+            attribute("beginDate", "2016-06-30"),
+            Attribute::BeginDate(Typed::new("2016-06-30", Date::new(2016, 6, 30).unwrap()))
+        );
+        assert_eq!(
+            attribute("orientation", "landscape"),
+            Attribute::Orientation(Typed::new("landscape", Orientation::Landscape))
+        );
+        assert_eq!(
+            attribute("partial", "1"),
+            Attribute::Partial(Typed::new("1", true))
+        );
+        assert_eq!(
+            attribute("class", "indent1  firstIndent0"),
+            Attribute::Class(Typed::new(
+                "indent1  firstIndent0",
+                vec!["indent1", "firstIndent0"]
+            ))
+        );
+        assert_eq!(
+            attribute(
+                "xsi:schemaLocation",
+                "http://schemas.gpo.gov/xml/uslm https://www.govinfo.gov/schemas/xml/uslm/uslm-2.1.0.xsd"
+            ),
+            Attribute::XsiSchemaLocation(Typed::new(
+                "http://schemas.gpo.gov/xml/uslm https://www.govinfo.gov/schemas/xml/uslm/uslm-2.1.0.xsd",
+                vec![(
+                    Url::parse("http://schemas.gpo.gov/xml/uslm").unwrap(),
+                    Url::parse("https://www.govinfo.gov/schemas/xml/uslm/uslm-2.1.0.xsd").unwrap()
+                )]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_attributes_lookup() {
+        let mut attributes: Attributes = vec![
+            Attribute::Id("s1"),
+            Attribute::Role("a"),
+            Attribute::Role("b"),
+        ]
+        .into();
+
+        assert_eq!(attributes.get::<kind::Role>(), Some(&"a"));
+        assert_eq!(attributes.get::<kind::Href>(), None);
+        assert_eq!(
+            attributes.set(Attribute::Role("c")),
+            Some(Attribute::Role("a"))
+        );
+        assert_eq!(
+            attributes.remove::<kind::Id>(),
+            Some((0, Attribute::Id("s1")))
+        );
+        assert_eq!(attributes.get::<kind::Role>(), Some(&"c"));
+        assert_eq!(
+            attributes.remove::<kind::Role>(),
+            Some((0, Attribute::Role("c")))
+        );
+        assert_eq!(attributes.get::<kind::Role>(), Some(&"b"));
+        assert_eq!(attributes[0].name(), "role");
+    }
+
+    #[test]
+    fn test_invalid_typed_attributes() {
+        let error = |key, value| Attribute::try_from((key, value)).unwrap_err().to_string();
+
+        assert_eq!(
+            error("rowspan", "two"),
+            "invalid value `two`: invalid digit found in string"
+        );
+        assert_eq!(
+            error("date", "June 30"),
+            "invalid value `June 30`: `June 30` is not a YYYY-MM-DD date"
+        );
+        assert_eq!(
+            error("orientation", "wide"),
+            "invalid value `wide`: expected `portrait` or `landscape`"
+        );
+        assert_eq!(
+            error("display", "yes"),
+            "invalid value `yes`: expected a boolean"
+        );
+        assert_eq!(
+            error("xsi:schemaLocation", "http://schemas.gpo.gov/xml/uslm"),
+            "invalid value `http://schemas.gpo.gov/xml/uslm`: expected pairs of a namespace and a location"
+        );
+        assert_eq!(
+            error("type", "css"),
+            "mime parse error: a slash (/) was missing between the type and subtype"
+        );
+        assert_eq!(error("colour", "red"), "unrecognized attribute `colour`");
     }
 }
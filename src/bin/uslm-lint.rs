@@ -0,0 +1,107 @@
+//! Checks USLM documents for structural errors, broken references and
+//! breaches of house style.
+//!
+//! ```text
+//! uslm-lint [--config lint.toml] [--format text|json] FILE...
+//! ```
+//!
+//! Exits with 1 when any error is reported, and 2 when the arguments,
+//! the configuration or a document cannot be read.
+//!
+//! The configuration format is described at `uslm_rs::LintConfig`.
+
+use std::{env, fs, process::ExitCode};
+
+use uslm_rs::{Diagnostic, LineIndex, Linter, Severity, Uslm};
+
+const USAGE: &str = "usage: uslm-lint [--config lint.toml] [--format text|json] FILE...";
+
+const CONFIG: &str =
+    "The --config file is a subset of TOML, see the documentation of `uslm_rs::LintConfig`.";
+
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("uslm-lint: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Whether the documents are free of errors.
+fn run(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut linter = Linter::new();
+    let mut format = Format::Text;
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => {
+                let path = args.next().ok_or(USAGE)?;
+                let config =
+                    fs::read_to_string(&path).map_err(|error| format!("{path}: {error}"))?;
+                let config = config.parse().map_err(|error| format!("{path}: {error}"))?;
+                linter
+                    .configure(&config)
+                    .map_err(|error| format!("{path}: {error}"))?;
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err(USAGE.to_string()),
+                }
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}\n\n{CONFIG}");
+                return Ok(true);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option `{arg}`\n{USAGE}")),
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let mut clean = true;
+    for file in &files {
+        let source = fs::read_to_string(file).map_err(|error| format!("{file}: {error}"))?;
+        let mut input = source.as_str();
+        let uslm = Uslm::parse(&mut input).map_err(|error| match error.into_inner() {
+            Ok(error) if error.cause().is_some() => format!("{file}: {error}"),
+            _ => format!("{file}: not a USLM document"),
+        })?;
+
+        let mut diagnostics: Vec<Diagnostic> = uslm.validate();
+        diagnostics.extend(uslm.check_integrity());
+        diagnostics.extend(linter.lint(&uslm));
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+
+        let index = LineIndex::new(&source);
+        for diagnostic in &diagnostics {
+            clean &= diagnostic.severity != Severity::Error;
+            match format {
+                Format::Json => println!("{}", diagnostic.to_json(file, &index)),
+                Format::Text => {
+                    let position = index.position(diagnostic.span.start);
+                    println!(
+                        "{file}:{}:{}: {}[{}]: {}",
+                        position.line,
+                        position.column,
+                        diagnostic.severity.name(),
+                        diagnostic.code,
+                        diagnostic.message
+                    );
+                }
+            }
+        }
+    }
+    Ok(clean)
+}
@@ -0,0 +1,444 @@
+use std::borrow::Cow;
+
+use url::Url;
+
+use crate::{
+    attributes::{Attribute, Encoding, Version},
+    edit::Arena,
+    header::{Header, XmlDeclaration},
+    span::Spans,
+    tags::{Core, Dc, Doc, Level, Meta, Other, Primitive, Property, Tag, TagType},
+    Uslm,
+};
+
+const USLM_NAMESPACE: &str = "http://schemas.gpo.gov/xml/uslm";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// Builds a bill from scratch, with the XML declaration, namespaces, a
+/// `meta` block and an `id` and `identifier` for every provision. The
+/// attribute values of the document are stored in an [`Arena`], see
+/// [`BillBuilder::build`].
+///
+/// Identifiers start from the congress, as in `/us/bill/118/hr/1234/s2`,
+/// or `/us/bill/118/s2` while the bill has no number. Without a congress
+/// provisions only get an `id`.
+#[derive(Debug, Clone, Default)]
+pub struct BillBuilder {
+    congress: Option<u16>,
+    session: Option<u8>,
+    /// The bill type, as in `hr` or `s`, and number.
+    number: Option<(String, u32)>,
+    title: Option<String>,
+    sections: Vec<LevelBuilder>,
+}
+
+impl BillBuilder {
+    pub fn new() -> Self {
+        BillBuilder::default()
+    }
+
+    pub fn congress(mut self, congress: u16) -> Self {
+        self.congress = Some(congress);
+        self
+    }
+
+    pub fn session(mut self, session: u8) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// The bill type and number, as in `number("hr", 1234)` for H.R. 1234.
+    pub fn number(mut self, bill_type: &str, number: u32) -> Self {
+        self.number = Some((bill_type.to_string(), number));
+        self
+    }
+
+    /// The `dc:title` of the bill.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Adds a section numbered `num`, filled in by `build`.
+    pub fn section(
+        mut self,
+        num: &str,
+        heading: &str,
+        build: impl FnOnce(LevelBuilder) -> LevelBuilder,
+    ) -> Self {
+        let section = LevelBuilder::new(Level::Section, num).heading(heading);
+        self.sections.push(build(section));
+        self
+    }
+
+    /// `/us/bill/118/hr/1234`, or `/us/bill/118` without a number.
+    fn document_identifier(&self) -> Option<String> {
+        let congress = self.congress?;
+        Some(match &self.number {
+            Some((bill_type, number)) => format!("/us/bill/{congress}/{bill_type}/{number}"),
+            None => format!("/us/bill/{congress}"),
+        })
+    }
+
+    /// The bill, with its attribute values stored in `arena`. The `id`s and
+    /// identifiers are generated here, from the congress and number set by
+    /// then.
+    pub fn build<'s>(self, arena: &'s Arena) -> Uslm<'s> {
+        let identifier = self.document_identifier();
+
+        let mut attributes = vec![
+            Attribute::Xmlns(Url::parse(USLM_NAMESPACE).expect("valid namespace")),
+            Attribute::XmlnsDc(Url::parse(DC_NAMESPACE).expect("valid namespace")),
+            Attribute::XmlLang("en"),
+        ];
+        if let Some(identifier) = &identifier {
+            attributes.push(Attribute::Identifier(arena.alloc(identifier.as_str())));
+        }
+
+        let mut meta = Vec::new();
+        if let Some(title) = &self.title {
+            meta.push(text_tag(TagType::Meta(Meta::Dc(Dc::Title)), title.clone()));
+        }
+        if let Some((bill_type, number)) = &self.number {
+            let chamber = match bill_type.as_str() {
+                "hr" => Some("House Bill"),
+                "s" => Some("Senate Bill"),
+                _ => None,
+            };
+            if let Some(chamber) = chamber {
+                meta.push(text_tag(
+                    TagType::Meta(Meta::Dc(Dc::Type)),
+                    chamber.to_string(),
+                ));
+            }
+            meta.push(text_tag(
+                TagType::Property(Property::DocNumber),
+                number.to_string(),
+            ));
+        }
+        if let Some(congress) = self.congress {
+            meta.push(text_tag(
+                TagType::Meta(Meta::Congress),
+                congress.to_string(),
+            ));
+        }
+        if let Some(session) = self.session {
+            meta.push(text_tag(TagType::Meta(Meta::Session), session.to_string()));
+        }
+
+        let bill = Tag {
+            tag_type: TagType::Doc(Doc::Bill),
+            attributes: attributes.into(),
+            content: None,
+            children: vec![
+                tag(TagType::Core(Core::Meta), vec![], meta),
+                tag(
+                    TagType::Core(Core::Main),
+                    vec![],
+                    self.sections
+                        .iter()
+                        .map(|section| section.tag(arena, identifier.as_deref(), None))
+                        .collect(),
+                ),
+            ],
+            spans: Spans::default(),
+        };
+        Uslm {
+            header: Header {
+                declaration: Some(XmlDeclaration {
+                    version: Version::One,
+                    encoding: Some(Encoding::Utf8),
+                    standalone: None,
+                }),
+                ..Header::default()
+            },
+            content: vec![bill],
+        }
+    }
+}
+
+/// Builds one provision of a [`BillBuilder`] and the provisions nested in
+/// it.
+#[derive(Debug, Clone)]
+pub struct LevelBuilder {
+    level: Level,
+    num: String,
+    heading: Option<String>,
+    body: Vec<Body>,
+}
+
+#[derive(Debug, Clone)]
+enum Body {
+    Text(TagType, String),
+    Level(LevelBuilder),
+}
+
+impl LevelBuilder {
+    fn new(level: Level, num: &str) -> Self {
+        LevelBuilder {
+            level,
+            num: num.to_string(),
+            heading: None,
+            body: Vec::new(),
+        }
+    }
+
+    pub fn heading(mut self, heading: &str) -> Self {
+        self.heading = Some(heading.to_string());
+        self
+    }
+
+    /// The text introducing the nested provisions.
+    pub fn chapeau(mut self, text: &str) -> Self {
+        self.body
+            .push(Body::Text(TagType::Other(Other::Chapeau), text.to_string()));
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.body.push(Body::Text(
+            TagType::Primitive(Primitive::Content),
+            text.to_string(),
+        ));
+        self
+    }
+
+    /// Adds a nested provision numbered `num`, filled in by `build`.
+    pub fn level(
+        mut self,
+        level: Level,
+        num: &str,
+        build: impl FnOnce(LevelBuilder) -> LevelBuilder,
+    ) -> Self {
+        let child = LevelBuilder::new(level, num);
+        self.body.push(Body::Level(build(child)));
+        self
+    }
+
+    pub fn subsection(self, num: &str, build: impl FnOnce(LevelBuilder) -> LevelBuilder) -> Self {
+        self.level(Level::Subsection, num, build)
+    }
+
+    pub fn paragraph(self, num: &str, build: impl FnOnce(LevelBuilder) -> LevelBuilder) -> Self {
+        self.level(Level::Paragraph, num, build)
+    }
+
+    pub fn subparagraph(self, num: &str, build: impl FnOnce(LevelBuilder) -> LevelBuilder) -> Self {
+        self.level(Level::Subparagraph, num, build)
+    }
+
+    pub fn clause(self, num: &str, build: impl FnOnce(LevelBuilder) -> LevelBuilder) -> Self {
+        self.level(Level::Clause, num, build)
+    }
+
+    /// The provision and the provisions nested in it, with an `id` and
+    /// identifier under those of its parent.
+    fn tag<'s>(
+        &self,
+        arena: &'s Arena,
+        parent_identifier: Option<&str>,
+        parent_id: Option<&str>,
+    ) -> Tag<'s> {
+        let designation = match self.level {
+            Level::Section => format!("s{}", self.num),
+            _ => self.num.clone(),
+        };
+        let identifier =
+            parent_identifier.map(|parent| arena.alloc(format!("{parent}/{designation}")));
+        let id = arena.alloc(match parent_id {
+            Some(parent) => format!("{parent}_{designation}"),
+            None => designation,
+        });
+        let mut attributes = vec![Attribute::Id(id)];
+        if let Some(identifier) = identifier {
+            attributes.push(Attribute::Identifier(identifier));
+        }
+
+        // Sections are numbered "SEC. 2." and the levels below "(a)".
+        let display = match self.level {
+            Level::Section => format!("SEC. {}.", self.num),
+            _ => format!("({})", self.num),
+        };
+        let mut children = vec![tag(
+            TagType::Core(Core::Num),
+            vec![Attribute::Value(arena.alloc(self.num.as_str()))],
+            vec![],
+        )];
+        children[0].content = Some(Cow::Owned(display));
+        if let Some(heading) = &self.heading {
+            children.push(text_tag(TagType::Core(Core::Heading), heading.clone()));
+        }
+        children.extend(self.body.iter().map(|body| match body {
+            Body::Text(tag_type, text) => text_tag(tag_type.clone(), text.clone()),
+            Body::Level(level) => level.tag(arena, identifier, Some(id)),
+        }));
+        tag(TagType::Level(self.level), attributes, children)
+    }
+}
+
+fn tag<'s>(tag_type: TagType, attributes: Vec<Attribute<'s>>, children: Vec<Tag<'s>>) -> Tag<'s> {
+    Tag {
+        tag_type,
+        attributes: attributes.into(),
+        content: None,
+        children,
+        spans: Spans::default(),
+    }
+}
+
+fn text_tag<'s>(tag_type: TagType, text: String) -> Tag<'s> {
+    Tag {
+        content: Some(Cow::Owned(text)),
+        ..tag(tag_type, vec![], vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn builder() -> BillBuilder {
+        BillBuilder::new()
+            .congress(118)
+            .session(1)
+            .number("hr", 1234)
+            .title("To establish an example program.")
+            .section("1", "Short title", |s| {
+                s.text("This Act may be cited as the Example Act.")
+            })
+            .section("2", "Definitions", |s| {
+                s.chapeau("In this Act:").paragraph("1", |p| {
+                    p.heading("Secretary")
+                        .text("The term Secretary means the Secretary of Commerce.")
+                })
+            })
+    }
+
+    #[test]
+    fn test_bill_builder() {
+        let arena = Arena::new();
+        let builder = builder();
+
+        let output = builder.build(&arena);
+
+        let bill = &output.content[0];
+        let section = &bill.children[1].children[1];
+        let paragraph = &section.children[3];
+        assert_eq!(output.validate(), vec![]);
+        assert!(bill
+            .attributes
+            .contains(&Attribute::Identifier("/us/bill/118/hr/1234")));
+        assert_eq!(
+            bill.children[0]
+                .children
+                .iter()
+                .map(|tag| (tag.tag_type.name(), tag.text()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("dc:title", "To establish an example program.".to_string()),
+                ("dc:type", "House Bill".to_string()),
+                ("docNumber", "1234".to_string()),
+                ("congress", "118".to_string()),
+                ("session", "1".to_string()),
+            ]
+        );
+        assert_eq!(
+            section.attributes,
+            vec![
+                Attribute::Id("s2"),
+                Attribute::Identifier("/us/bill/118/hr/1234/s2")
+            ]
+        );
+        assert_eq!(
+            paragraph.attributes,
+            vec![
+                Attribute::Id("s2_1"),
+                Attribute::Identifier("/us/bill/118/hr/1234/s2/1")
+            ]
+        );
+        assert_eq!(
+            section.text(),
+            "SEC. 2. Definitions In this Act: (1) Secretary The term Secretary means the Secretary of Commerce."
+        );
+    }
+
+    #[test]
+    fn test_bill_builder_without_number() {
+        let arena = Arena::new();
+        let builder =
+            BillBuilder::new()
+                .congress(118)
+                .session(1)
+                .section("1", "Short title", |s| s.text("Example Act"));
+
+        let output = builder.build(&arena);
+
+        let section = &output.content[0].children[1].children[0];
+        assert_eq!(
+            section.attributes,
+            vec![
+                Attribute::Id("s1"),
+                Attribute::Identifier("/us/bill/118/s1")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bill_builder_number_after_sections() {
+        let arena = Arena::new();
+        let builder = BillBuilder::new()
+            .section("1", "Short title", |s| {
+                s.subsection("a", |s| s.text("Example Act"))
+            })
+            .congress(118)
+            .number("s", 42);
+
+        let output = builder.build(&arena);
+
+        let subsection = &output.content[0].children[1].children[0].children[2];
+        assert_eq!(
+            subsection.attributes,
+            vec![
+                Attribute::Id("s1_a"),
+                Attribute::Identifier("/us/bill/118/s/42/s1/a")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bill_builder_without_congress() {
+        let arena = Arena::new();
+        let builder = BillBuilder::new().section("1", "Short title", |s| s.text("Example Act"));
+
+        let output = builder.build(&arena);
+
+        let section = &output.content[0].children[1].children[0];
+        assert_eq!(section.attributes, vec![Attribute::Id("s1")]);
+        assert_eq!(output.content[0].children[0].children, vec![]);
+    }
+
+    #[test]
+    fn test_bill_builder_chain() {
+        let arena = Arena::new();
+
+        let output = BillBuilder::new()
+            .congress(118)
+            .session(1)
+            .section("1", "Short title", |s| {
+                s.text("This Act may be cited as the Example Act.")
+            })
+            .build(&arena);
+
+        let section = &output.content[0].children[1].children[0];
+        assert_eq!(output.validate(), vec![]);
+        assert_eq!(
+            section.attributes,
+            vec![
+                Attribute::Id("s1"),
+                Attribute::Identifier("/us/bill/118/s1")
+            ]
+        );
+    }
+}
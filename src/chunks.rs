@@ -0,0 +1,313 @@
+use crate::{
+    span::Span,
+    tags::{Core, Level, Tag, TagType},
+    Uslm,
+};
+
+/// A retrievable piece of a document, covering one level and everything
+/// nested inside it, or only the text a coarser level holds next to its
+/// sub-levels.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Chunk<'s> {
+    pub level: Level,
+    pub identifier: Option<&'s str>,
+    /// Number and heading of every enclosing level, outermost first.
+    pub breadcrumb: Vec<String>,
+    pub text: String,
+    pub span: Span,
+}
+
+/// Iterator over the chunks of a document, see [`Uslm::chunks`].
+pub struct Chunks<'a, 's> {
+    granularity: Level,
+    breadcrumb: Vec<String>,
+    /// Tags left to visit, with the breadcrumb depth they were found at.
+    stack: Vec<(&'a Tag<'s>, usize)>,
+}
+
+impl<'s> Uslm<'s> {
+    /// Splits the document into one chunk per level of the `granularity`
+    /// kind. Finer levels reached without passing through one, and levels
+    /// with no sub-levels at all, are chunked on their own. A coarser level
+    /// with text of its own next to its sub-levels, as the `chapeau` or a
+    /// `note` of a chapter, gets a chunk for its number, heading and that
+    /// text, coming before the chunks of its sub-levels.
+    pub fn chunks(&self, granularity: Level) -> Chunks<'_, 's> {
+        Chunks {
+            granularity,
+            breadcrumb: Vec::new(),
+            stack: self.content.iter().rev().map(|tag| (tag, 0)).collect(),
+        }
+    }
+}
+
+impl<'s> Iterator for Chunks<'_, 's> {
+    type Item = Chunk<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((tag, depth)) = self.stack.pop() {
+            self.breadcrumb.truncate(depth);
+            let level = match tag.tag_type {
+                TagType::Level(level) => Some(level),
+                _ => None,
+            };
+            if let Some(level) = level {
+                if self.is_chunk(level) || !has_sublevels(tag) {
+                    return Some(Chunk {
+                        level,
+                        identifier: tag.identifier(),
+                        breadcrumb: self.breadcrumb.clone(),
+                        text: tag.text(),
+                        span: tag.span(),
+                    });
+                }
+            }
+            let leftover = level.and_then(|level| {
+                let (text, span) = leftover(tag)?;
+                Some(Chunk {
+                    level,
+                    identifier: tag.identifier(),
+                    breadcrumb: self.breadcrumb.clone(),
+                    text,
+                    span,
+                })
+            });
+            if level.is_some() || tag.tag_type == TagType::Core(Core::Level) {
+                self.breadcrumb.push(label(tag));
+            }
+            let depth = self.breadcrumb.len();
+            self.stack
+                .extend(tag.children.iter().rev().map(|child| (child, depth)));
+            if leftover.is_some() {
+                return leftover;
+            }
+        }
+        None
+    }
+}
+
+impl Chunks<'_, '_> {
+    /// Whether `level` is as fine as the granularity. Levels without a
+    /// rank, as `courtRules` or `compiledAct`, only match themselves and
+    /// are otherwise containers to look into.
+    fn is_chunk(&self, level: Level) -> bool {
+        match (level.rank(), self.granularity.rank()) {
+            (Some(rank), Some(granularity)) => rank >= granularity,
+            _ => level == self.granularity,
+        }
+    }
+}
+
+fn has_sublevels(tag: &Tag) -> bool {
+    tag.children
+        .iter()
+        .any(|child| matches!(child.tag_type, TagType::Level(_)) || has_sublevels(child))
+}
+
+/// The label of a level with the text of its children that hold no levels,
+/// and the span from the first to the last of those children. `None` when
+/// the level has no such text.
+fn leftover(tag: &Tag) -> Option<(String, Span)> {
+    let children: Vec<&Tag> = tag
+        .children
+        .iter()
+        .filter(|child| {
+            !matches!(
+                child.tag_type,
+                TagType::Level(_) | TagType::Core(Core::Level | Core::Num | Core::Heading)
+            ) && !has_sublevels(child)
+        })
+        .filter(|child| !child.text().is_empty())
+        .collect();
+    let span = Span::new(children.first()?.span().start, children.last()?.span().end);
+    let mut text = label(tag);
+    for child in children {
+        text.push(' ');
+        text.push_str(&child.text());
+    }
+    Some((text.trim_start().to_string(), span))
+}
+
+/// The `num` and `heading` of a level, as in "CHAPTER 1. General Provisions".
+fn label(tag: &Tag) -> String {
+    tag.children
+        .iter()
+        .filter(|child| matches!(child.tag_type, TagType::Core(Core::Num | Core::Heading)))
+        .map(Tag::text)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<uscDoc identifier="/us/usc/t5">
+<main>
+<title identifier="/us/usc/t5">
+<num value="5">Title 5.</num>
+<heading>Government Organization and Employees</heading>
+<chapter identifier="/us/usc/t5/ch5">
+<num value="5">CHAPTER 5.</num>
+<heading>Administrative Procedure</heading>
+<section identifier="/us/usc/t5/s551">
+<num value="551">Sec. 551.</num>
+<heading>Definitions</heading>
+<content>For the purpose of this subchapter</content>
+</section>
+<section identifier="/us/usc/t5/s552">
+<num value="552">Sec. 552.</num>
+<heading>Public information</heading>
+<subsection identifier="/us/usc/t5/s552/a">
+<num value="a">a.</num>
+<content>Each agency shall make available to the public information</content>
+</subsection>
+<subsection identifier="/us/usc/t5/s552/b">
+<num value="b">b.</num>
+<content>This section does not apply to matters that are</content>
+</subsection>
+</section>
+</chapter>
+</title>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_section_chunks() {
+        let mut input = INPUT;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output: Vec<_> = uslm.chunks(Level::Section).collect();
+
+        let start = INPUT
+            .find("<section identifier=\"/us/usc/t5/s551\">")
+            .unwrap();
+        let end = INPUT
+            .find("<section identifier=\"/us/usc/t5/s552\">")
+            .unwrap()
+            - 1;
+        assert_eq!(output.len(), 2);
+        assert_eq!(
+            output[0],
+            Chunk {
+                level: Level::Section,
+                identifier: Some("/us/usc/t5/s551"),
+                breadcrumb: vec![
+                    "Title 5. Government Organization and Employees".to_string(),
+                    "CHAPTER 5. Administrative Procedure".to_string()
+                ],
+                text: "Sec. 551. Definitions For the purpose of this subchapter".to_string(),
+                span: Span::new(start, end),
+            }
+        );
+        assert!(INPUT[output[0].span.start..output[0].span.end].ends_with("</section>"));
+        assert_eq!(output[1].identifier, Some("/us/usc/t5/s552"));
+    }
+
+    #[test]
+    fn test_subsection_chunks() {
+        let mut input = INPUT;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output: Vec<_> = uslm
+            .chunks(Level::Subsection)
+            .map(|chunk| (chunk.identifier, chunk.breadcrumb.len(), chunk.text))
+            .collect();
+
+        assert_eq!(
+            output,
+            vec![
+                (
+                    Some("/us/usc/t5/s551"),
+                    2,
+                    "Sec. 551. Definitions For the purpose of this subchapter".to_string()
+                ),
+                (
+                    Some("/us/usc/t5/s552/a"),
+                    3,
+                    "a. Each agency shall make available to the public information".to_string()
+                ),
+                (
+                    Some("/us/usc/t5/s552/b"),
+                    3,
+                    "b. This section does not apply to matters that are".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunks_keep_text_beside_sublevels() {
+        let mut input = r#"<uscDoc><main><chapter identifier="/us/usc/t5/ch5">
+<num value="5">CHAPTER 5.</num><heading>Administrative Procedure</heading>
+<chapeau>This chapter governs agencies.</chapeau>
+<section identifier="/us/usc/t5/s551"><num value="551">Sec. 551.</num><content>Definitions</content></section>
+<note><content>Editorial note.</content></note>
+</chapter></main></uscDoc>"#;
+        let source = input;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output: Vec<_> = uslm
+            .chunks(Level::Section)
+            .map(|chunk| (chunk.level, chunk.identifier, chunk.text, chunk.span))
+            .collect();
+
+        let start = source.find("<chapeau>").unwrap();
+        let end = source.find("</note>").unwrap() + "</note>".len();
+        assert_eq!(
+            output,
+            vec![
+                (
+                    Level::Chapter,
+                    Some("/us/usc/t5/ch5"),
+                    "CHAPTER 5. Administrative Procedure This chapter governs agencies. Editorial note."
+                        .to_string(),
+                    Span::new(start, end)
+                ),
+                (
+                    Level::Section,
+                    Some("/us/usc/t5/s551"),
+                    "Sec. 551. Definitions".to_string(),
+                    uslm.content[0].children[0].children[0].children[3].span()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunks_inside_unranked_levels() {
+        let mut input = r#"<uscDoc><main><courtRules identifier="/us/usc/t28/courtRules">
+<heading>Rules of Evidence</heading>
+<courtRule identifier="/us/usc/t28/courtRules/r101"><num value="101">Rule 101.</num>
+<section identifier="/us/usc/t28/courtRules/r101/s1"><num value="1">Sec. 1.</num><content>Scope</content></section>
+<section identifier="/us/usc/t28/courtRules/r101/s2"><num value="2">Sec. 2.</num><content>Definitions</content></section>
+</courtRule>
+</courtRules></main></uscDoc>"#;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let sections: Vec<_> = uslm
+            .chunks(Level::Section)
+            .map(|chunk| chunk.identifier)
+            .collect();
+        let rules: Vec<_> = uslm
+            .chunks(Level::CourtRule)
+            .map(|chunk| chunk.identifier)
+            .collect();
+
+        assert_eq!(
+            sections,
+            vec![
+                Some("/us/usc/t28/courtRules/r101/s1"),
+                Some("/us/usc/t28/courtRules/r101/s2"),
+            ]
+        );
+        assert_eq!(rules, vec![Some("/us/usc/t28/courtRules/r101")]);
+    }
+}
@@ -0,0 +1,383 @@
+use std::{borrow::Cow, ops::Range};
+
+use winnow::{
+    ascii::{digit1, space0, space1},
+    combinator::{alt, delimited, opt, preceded, repeat},
+    stream::Offset,
+    token::{literal, take_while},
+    ModalResult, Parser,
+};
+
+use crate::{
+    attributes::Attribute,
+    reference::UslmRef,
+    span::{Span, Spans},
+    tags::{Core, Primitive, Tag, TagType},
+    Uslm,
+};
+
+/// A citation recognized in plain text, such as "42 U.S.C. 1983", with
+/// the USLM reference it stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Citation {
+    pub href: String,
+    pub span: Span,
+}
+
+impl Citation {
+    /// The parsed `href`, `None` when it is not an absolute reference,
+    /// which the citations found by [`citations`] always are.
+    pub fn target(&self) -> Option<UslmRef<'_>> {
+        UslmRef::parse(&self.href)
+    }
+}
+
+/// Finds the citations of the United States Code, Public Laws and the
+/// Statutes at Large in `text`, with spans relative to `text`:
+///
+/// - "section 552(b)(3) of title 5, United States Code"
+/// - "42 U.S.C. 1395w–4(a)(2)"
+/// - "Public Law 117–58" and "Pub. L. 117–58"
+/// - "135 Stat. 429"
+pub fn citations(text: &str) -> Vec<Citation> {
+    let mut citations = Vec::new();
+    let mut previous = None;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let at_word_start = !previous.is_some_and(|c: char| c.is_alphanumeric());
+        let mut input = rest;
+        if let Some(href) = at_word_start.then(|| citation(&mut input).ok()).flatten() {
+            let start = rest.offset_from(&text);
+            let end = input.offset_from(&text);
+            citations.push(Citation {
+                href,
+                span: Span::new(start, end),
+            });
+            previous = text[..end].chars().next_back();
+            rest = input;
+        } else {
+            let mut chars = rest.chars();
+            previous = chars.next();
+            rest = chars.as_str();
+        }
+    }
+    citations
+}
+
+fn citation(input: &mut &str) -> ModalResult<String> {
+    alt((section_of_title, usc, public_law, statutes_at_large)).parse_next(input)
+}
+
+/// "section 552(b)(3) of title 5", optionally followed by ", United
+/// States Code".
+fn section_of_title(input: &mut &str) -> ModalResult<String> {
+    let (_, section, paragraphs, _, title, _) = (
+        (alt(("section", "Section")), space1),
+        designation,
+        paragraphs,
+        (space1, "of", space1, "title", space1),
+        digit1,
+        opt(", United States Code"),
+    )
+        .parse_next(input)?;
+    Ok(format!("/us/usc/t{title}/s{section}{paragraphs}"))
+}
+
+/// "42 U.S.C. 1395w–4(a)(2)", with or without a section sign.
+fn usc(input: &mut &str) -> ModalResult<String> {
+    let (title, _, section, paragraphs) = (
+        digit1,
+        (space1, "U.S.C.", space1, opt(("§", opt("§"), space0))),
+        designation,
+        paragraphs,
+    )
+        .parse_next(input)?;
+    Ok(format!("/us/usc/t{title}/s{section}{paragraphs}"))
+}
+
+/// "Public Law 117–58" or "Pub. L. 117–58".
+fn public_law(input: &mut &str) -> ModalResult<String> {
+    let (congress, number) = preceded(
+        (alt(("Public Law", "Pub. L.")), space1),
+        (digit1, preceded(alt(('–', '-')), digit1)),
+    )
+    .parse_next(input)?;
+    Ok(format!("/us/pl/{congress}/{number}"))
+}
+
+/// "135 Stat. 429".
+fn statutes_at_large(input: &mut &str) -> ModalResult<String> {
+    let (volume, _, page) =
+        (digit1, (space1, literal("Stat."), space1), digit1).parse_next(input)?;
+    Ok(format!("/us/stat/{volume}/{page}"))
+}
+
+/// A section designation such as "1395w–4", with dashes normalized to
+/// hyphens.
+fn designation(input: &mut &str) -> ModalResult<String> {
+    let designation = (
+        take_while(1.., |c: char| c.is_ascii_alphanumeric()),
+        take_while(0.., |c: char| {
+            c.is_ascii_alphanumeric() || c == '-' || c == '–'
+        }),
+    )
+        .take()
+        .parse_next(input)?;
+    Ok(designation.trim_end_matches(['-', '–']).replace('–', "-"))
+}
+
+/// Paragraph designations such as "(b)(3)", as the path "/b/3".
+fn paragraphs(input: &mut &str) -> ModalResult<String> {
+    repeat(
+        0..,
+        delimited(
+            '(',
+            take_while(1.., |c: char| c.is_ascii_alphanumeric()),
+            ')',
+        ),
+    )
+    .fold(String::new, |mut path, paragraph: &str| {
+        path.push('/');
+        path.push_str(paragraph);
+        path
+    })
+    .parse_next(input)
+}
+
+impl<'s> Uslm<'s> {
+    /// The citations in the text of the document outside existing `ref`
+    /// elements, with spans into the source document.
+    pub fn citations(&self) -> Vec<Citation> {
+        let mut found = Vec::new();
+        let mut stack: Vec<&Tag> = self.content.iter().collect();
+        while let Some(tag) = stack.pop() {
+            if tag.tag_type == TagType::Core(Core::Ref) {
+                continue;
+            }
+            if let (Some(content), Some(span)) = (&tag.content, tag.spans.content) {
+                found.extend(citations(content).into_iter().map(|citation| Citation {
+                    href: citation.href,
+                    span: Span::new(
+                        span.start + citation.span.start,
+                        span.start + citation.span.end,
+                    ),
+                }));
+            }
+            stack.extend(&tag.children);
+        }
+        found.sort_by_key(|citation| citation.span.start);
+        found
+    }
+
+    /// A copy of the document with each of `citations` wrapped in a `ref`
+    /// to its `href`. Tags only carry the text following their children,
+    /// so the text before a citation moves into an `inline` element.
+    pub fn link_citations<'a>(&self, citations: &'a [Citation]) -> Uslm<'a>
+    where
+        's: 'a,
+    {
+        let mut uslm: Uslm<'a> = self.clone();
+        for tag in &mut uslm.content {
+            link(tag, citations);
+        }
+        uslm
+    }
+}
+
+fn link<'a>(tag: &mut Tag<'a>, citations: &'a [Citation]) {
+    if tag.tag_type == TagType::Core(Core::Ref) {
+        return;
+    }
+    for child in &mut tag.children {
+        link(child, citations);
+    }
+    let (Some(content), Some(span)) = (tag.content.take(), tag.spans.content) else {
+        return;
+    };
+    // Content edited since parsing no longer lines up with the source.
+    if span.len() != content.len() {
+        tag.content = Some(content);
+        return;
+    }
+
+    let mut start = span.start;
+    for citation in citations
+        .iter()
+        .filter(|citation| span.start <= citation.span.start && citation.span.end <= span.end)
+    {
+        let text = |from: usize, to: usize| slice(&content, from - span.start..to - span.start);
+        if start < citation.span.start {
+            tag.children.push(text_tag(
+                TagType::Primitive(Primitive::Inline),
+                vec![],
+                text(start, citation.span.start),
+                Span::new(start, citation.span.start),
+            ));
+        }
+        tag.children.push(text_tag(
+            TagType::Core(Core::Ref),
+            vec![Attribute::Href(&citation.href)],
+            text(citation.span.start, citation.span.end),
+            citation.span,
+        ));
+        start = citation.span.end;
+    }
+    if start == span.start {
+        tag.content = Some(content);
+    } else if start < span.end {
+        tag.content = Some(slice(&content, start - span.start..content.len()));
+        tag.spans.content = Some(Span::new(start, span.end));
+    } else {
+        tag.spans.content = None;
+    }
+}
+
+/// Part of `content`, still borrowing from the source when it does.
+fn slice<'a>(content: &Cow<'a, str>, range: Range<usize>) -> Cow<'a, str> {
+    match content {
+        Cow::Borrowed(content) => Cow::Borrowed(&content[range]),
+        Cow::Owned(content) => Cow::Owned(content[range].to_string()),
+    }
+}
+
+fn text_tag<'a>(
+    tag_type: TagType,
+    attributes: Vec<Attribute<'a>>,
+    text: Cow<'a, str>,
+    span: Span,
+) -> Tag<'a> {
+    Tag {
+        tag_type,
+        attributes: attributes.into(),
+        content: Some(text),
+        children: Vec::new(),
+        spans: Spans {
+            element: span,
+            content: Some(span),
+            attributes: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn hrefs(text: &str) -> Vec<(String, &str)> {
+        citations(text)
+            .into_iter()
+            .map(|citation| (citation.href, &text[citation.span.start..citation.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_citations() {
+        let output = hrefs(
+            "as provided in section 552(b)(3) of title 5, United States Code, and 42 U.S.C. 1395w–4(a)(2), as amended by Public Law 117–58 at 135 Stat. 429.",
+        );
+
+        assert_eq!(
+            output,
+            vec![
+                (
+                    "/us/usc/t5/s552/b/3".to_string(),
+                    "section 552(b)(3) of title 5, United States Code"
+                ),
+                (
+                    "/us/usc/t42/s1395w-4/a/2".to_string(),
+                    "42 U.S.C. 1395w–4(a)(2)"
+                ),
+                ("/us/pl/117/58".to_string(), "Public Law 117–58"),
+                ("/us/stat/135/429".to_string(), "135 Stat. 429"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_citations_need_word_boundaries() {
+        let output = hrefs("Subsection 5 of title 5 and 2042 U.S.C.A. 12 and Pub. L. 89–554");
+
+        assert_eq!(
+            output,
+            vec![("/us/pl/89/554".to_string(), "Pub. L. 89–554")]
+        );
+    }
+
+    #[test]
+    fn test_citation_target() {
+        let found = citations("42 U.S.C. 1983");
+        let invalid = Citation {
+            href: "x".to_string(),
+            span: Span::new(0, 1),
+        };
+
+        assert_eq!(
+            found[0].target().map(|target| target.href),
+            Some("/us/usc/t42/s1983")
+        );
+        assert_eq!(invalid.target(), None);
+    }
+
+    fn children<'a>(tag: &Tag<'a>) -> Vec<(&'static str, Vec<Attribute<'a>>, String)> {
+        tag.children
+            .iter()
+            .map(|child| {
+                (
+                    child.tag_type.name(),
+                    child.attributes.to_vec(),
+                    child.text(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_link_citations() {
+        let input = r#"<bill><main><section><chapeau>Amends 5 U.S.C. 552(b) and</chapeau><content><ref href="/us/usc/t5/s551">section 551</ref> as enacted by Public Law 89–554 in 1966</content></section></main></bill>"#;
+        let mut source = input;
+        let uslm = Uslm::parse(&mut source).unwrap();
+
+        let citations = uslm.citations();
+        let output = uslm.link_citations(&citations);
+
+        let section = &output.content[0].children[0].children[0];
+        assert_eq!(
+            citations
+                .iter()
+                .map(|citation| &input[citation.span.start..citation.span.end])
+                .collect::<Vec<_>>(),
+            vec!["5 U.S.C. 552(b)", "Public Law 89–554"]
+        );
+        assert_eq!(
+            children(&section.children[0]),
+            vec![
+                ("inline", vec![], "Amends".to_string()),
+                (
+                    "ref",
+                    vec![Attribute::Href("/us/usc/t5/s552/b")],
+                    "5 U.S.C. 552(b)".to_string()
+                ),
+            ]
+        );
+        assert_eq!(section.children[0].content.as_deref(), Some(" and"));
+        assert_eq!(
+            children(&section.children[1]),
+            vec![
+                (
+                    "ref",
+                    vec![Attribute::Href("/us/usc/t5/s551")],
+                    "section 551".to_string()
+                ),
+                ("inline", vec![], "as enacted by".to_string()),
+                (
+                    "ref",
+                    vec![Attribute::Href("/us/pl/89/554")],
+                    "Public Law 89–554".to_string()
+                ),
+            ]
+        );
+        assert_eq!(section.children[1].content.as_deref(), Some(" in 1966"));
+    }
+}
@@ -1,5 +1,8 @@
+use std::{error::Error, fmt};
+
 use winnow::{
     combinator::{alt, delimited, opt, separated, separated_pair},
+    error::{ContextError, ErrMode, FromExternalError},
     token::take_while,
     ModalResult, Parser,
 };
@@ -8,25 +11,27 @@ pub(super) fn content<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
     take_while(
         1..,
         (
-            'a'..='z',
-            'A'..='Z',
-            '0'..='9',
-            '-',
-            '=',
-            '+',
-            '.',
-            ',',
-            '&',
-            ';',
-            '\n',
-            '\r',
-            '\t',
-            ' ',
-            ':',
-            '/',
-            '[',
-            ']',
-            '–',
+            (
+                'a'..='z',
+                'A'..='Z',
+                '0'..='9',
+                '-',
+                '=',
+                '+',
+                '.',
+                ',',
+                '&',
+                ';',
+                '\n',
+                '\r',
+                '\t',
+                ' ',
+                ':',
+                '/',
+                '[',
+                ']',
+            ),
+            ('(', ')', '–', '—', '§', '\'', '"', '‘', '’', '“', '”'),
         ),
     )
     .parse_next(input)
@@ -52,6 +57,8 @@ pub(super) fn val_text<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
             'A'..='Z',
             '0'..='9',
             '-',
+            '_',
+            '#',
             '.',
             '&',
             ';',
@@ -96,6 +103,62 @@ pub(crate) fn ws<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
 
 const WS: &[char] = &[' ', '\t', '\r', '\n'];
 
+/// Why well-formed markup could not be read, as in an attribute with an
+/// invalid value.
+#[derive(Debug)]
+struct Invalid(String);
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for Invalid {}
+
+/// An error that stops the parse, with `message` as its cause.
+pub(crate) fn invalid(input: &&str, message: impl fmt::Display) -> ErrMode<ContextError> {
+    ErrMode::Cut(ContextError::from_external_error(
+        input,
+        Invalid(message.to_string()),
+    ))
+}
+
+/// Escapes text for an XML or HTML attribute or element. Values from the
+/// document are still XML-escaped, so an `&` already starting a reference
+/// such as `&amp;` is kept as it is.
+pub(crate) fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for (index, c) in value.char_indices() {
+        match c {
+            '&' if !is_reference(&value[index..]) => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Whether `text` starts with an entity or character reference, as
+/// `&amp;`, `&#167;` or `&#xA7;`.
+fn is_reference(text: &str) -> bool {
+    let Some((name, _)) = text[1..].split_once(';') else {
+        return false;
+    };
+    match name.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => {
+            hex.len() > 1 && hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+        }
+        Some(digits) => !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()),
+        None => {
+            name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && name.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
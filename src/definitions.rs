@@ -0,0 +1,246 @@
+use std::str::FromStr;
+
+use crate::{
+    span::Span,
+    tags::{Level, Other, Tag, TagType},
+    Uslm,
+};
+
+/// A term defined by a `def` element, see [`Uslm::definitions`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Definition<'s> {
+    pub term: String,
+    /// The whole text of the `def`, term included.
+    pub text: String,
+    /// The `identifier` of the closest provision containing the `def`.
+    pub provision: Option<&'s str>,
+    /// Where the definition applies, when the enclosing chapeau says.
+    pub scope: Option<Scope<'s>>,
+    pub span: Span,
+}
+
+/// The part of a document a definition applies to, as in "In this
+/// section" or "For purposes of this title".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope<'s> {
+    pub extent: Extent,
+    /// The `identifier` of the enclosing provision of that extent. `None`
+    /// for the whole Act, or when the provision has no identifier.
+    pub provision: Option<&'s str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extent {
+    Level(Level),
+    Act,
+}
+
+impl Scope<'_> {
+    /// Whether the provision with `identifier` falls within the scope. A
+    /// scope without a provision covers everything.
+    pub fn contains(&self, identifier: &str) -> bool {
+        match self.provision {
+            Some(provision) => identifier
+                .strip_prefix(provision)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+            None => true,
+        }
+    }
+}
+
+impl<'s> Uslm<'s> {
+    /// Every term defined in the document, in document order. A `def`
+    /// defining several terms gives one definition per term.
+    pub fn definitions(&self) -> Vec<Definition<'s>> {
+        let mut definitions = Vec::new();
+        let mut ancestors = Vec::new();
+        for tag in &self.content {
+            collect(tag, &mut ancestors, &mut definitions);
+        }
+        definitions
+    }
+}
+
+fn collect<'a, 's>(
+    tag: &'a Tag<'s>,
+    ancestors: &mut Vec<&'a Tag<'s>>,
+    definitions: &mut Vec<Definition<'s>>,
+) {
+    if tag.tag_type == TagType::Other(Other::Def) {
+        let provision = ancestors.iter().rev().find_map(|tag| identifier(tag));
+        let scope = scope(ancestors);
+        let text = tag.text();
+        let mut terms = Vec::new();
+        find_terms(tag, &mut terms);
+        definitions.extend(terms.into_iter().map(|term| Definition {
+            term,
+            text: text.clone(),
+            provision,
+            scope: scope.clone(),
+            span: tag.span(),
+        }));
+    }
+    ancestors.push(tag);
+    for child in &tag.children {
+        collect(child, ancestors, definitions);
+    }
+    ancestors.pop();
+}
+
+fn find_terms(tag: &Tag, terms: &mut Vec<String>) {
+    for child in &tag.children {
+        if child.tag_type == TagType::Other(Other::Term) {
+            terms.push(child.text());
+        } else {
+            find_terms(child, terms);
+        }
+    }
+}
+
+fn identifier<'s>(tag: &Tag<'s>) -> Option<&'s str> {
+    match tag.tag_type {
+        TagType::Level(_) => tag.identifier(),
+        _ => None,
+    }
+}
+
+/// The scope announced by the closest chapeau among the ancestors of a
+/// definition, or among their children.
+fn scope<'s>(ancestors: &[&Tag<'s>]) -> Option<Scope<'s>> {
+    let extent = ancestors.iter().rev().find_map(|ancestor| {
+        std::iter::once(*ancestor)
+            .chain(&ancestor.children)
+            .filter(|tag| tag.tag_type == TagType::Other(Other::Chapeau))
+            .find_map(|chapeau| extent(&chapeau.text()))
+    })?;
+    let provision = match extent {
+        Extent::Level(level) => ancestors
+            .iter()
+            .rev()
+            .find(|ancestor| ancestor.tag_type == TagType::Level(level))
+            .and_then(|ancestor| identifier(ancestor)),
+        Extent::Act => None,
+    };
+    Some(Scope { extent, provision })
+}
+
+/// Reads the extent from phrases such as "In this section", "As used in
+/// this chapter" and "For purposes of this title".
+fn extent(text: &str) -> Option<Extent> {
+    const LEVELS: [&str; 11] = [
+        "title",
+        "subtitle",
+        "division",
+        "chapter",
+        "subchapter",
+        "part",
+        "subpart",
+        "section",
+        "subsection",
+        "paragraph",
+        "subparagraph",
+    ];
+    let text = text.to_lowercase();
+    ["in this ", "purpose of this ", "purposes of this "]
+        .iter()
+        .find_map(|phrase| {
+            let (_, rest) = text.split_once(phrase)?;
+            let word: String = rest.chars().take_while(|c| c.is_alphabetic()).collect();
+            match word.as_str() {
+                "act" => Some(Extent::Act),
+                word if LEVELS.contains(&word) => Level::from_str(word).ok().map(Extent::Level),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<chapter identifier="/us/usc/t5/ch5">
+<section identifier="/us/usc/t5/s551">
+<num value="551">Sec. 551.</num>
+<heading>Definitions</heading>
+<chapeau>For the purposes of this chapter–</chapeau>
+<paragraph identifier="/us/usc/t5/s551/1">
+<num value="1">1</num>
+<content><def><term>agency</term> means each authority of the Government of the United States</def></content>
+</paragraph>
+</section>
+<section identifier="/us/usc/t5/s552">
+<subsection identifier="/us/usc/t5/s552/f">
+<chapeau>In this section–</chapeau>
+<paragraph identifier="/us/usc/t5/s552/f/1">
+<content><def><term>record</term><term>information</term> include any information</def></content>
+</paragraph>
+</subsection>
+</section>
+<section identifier="/us/usc/t5/s553">
+<content><def><term>rule making</term> means agency process</def></content>
+</section>
+</chapter>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_definitions() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output: Vec<_> = uslm
+            .definitions()
+            .into_iter()
+            .map(|definition| (definition.term, definition.provision, definition.scope))
+            .collect();
+
+        assert_eq!(
+            output,
+            vec![
+                (
+                    "agency".to_string(),
+                    Some("/us/usc/t5/s551/1"),
+                    Some(Scope {
+                        extent: Extent::Level(Level::Chapter),
+                        provision: Some("/us/usc/t5/ch5"),
+                    })
+                ),
+                (
+                    "record".to_string(),
+                    Some("/us/usc/t5/s552/f/1"),
+                    Some(Scope {
+                        extent: Extent::Level(Level::Section),
+                        provision: Some("/us/usc/t5/s552"),
+                    })
+                ),
+                (
+                    "information".to_string(),
+                    Some("/us/usc/t5/s552/f/1"),
+                    Some(Scope {
+                        extent: Extent::Level(Level::Section),
+                        provision: Some("/us/usc/t5/s552"),
+                    })
+                ),
+                ("rule making".to_string(), Some("/us/usc/t5/s553"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_definition_text_and_scope() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.definitions();
+
+        let scope = output[1].scope.as_ref().unwrap();
+        assert_eq!(output[1].text, "record information include any information");
+        assert!(scope.contains("/us/usc/t5/s552/a"));
+        assert!(!scope.contains("/us/usc/t5/s5520"));
+        assert_eq!(extent("As used in this Act, the term"), Some(Extent::Act));
+    }
+}
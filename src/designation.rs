@@ -0,0 +1,413 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::{
+    attributes::Attribute,
+    tags::{Core, Tag, TagType},
+};
+
+/// The designation of a provision, such as "552", "a", "iii" or "II", read
+/// from the text or `value` of its `num`.
+///
+/// Designations order by their value, then by their suffix, so that "101"
+/// comes before "101a" and "1395w-4" before "1395w-10". The order agrees
+/// with equality: only identical designations compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Designation {
+    pub kind: DesignationKind,
+    /// The number the designation stands for, as in 1395 for "1395w-4", 3
+    /// for "c" and "iii", and 27 for "aa".
+    pub value: u32,
+    /// What follows the number, as in "w-4" for "1395w-4", with dashes
+    /// normalized to hyphens.
+    pub suffix: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DesignationKind {
+    Arabic,
+    LowerAlpha,
+    UpperAlpha,
+    LowerRoman,
+    UpperRoman,
+}
+
+impl Designation {
+    pub fn new(kind: DesignationKind, value: u32) -> Self {
+        Designation {
+            kind,
+            value,
+            suffix: String::new(),
+        }
+    }
+
+    /// Parses `text` knowing the designation of the previous sibling, which
+    /// tells "(i)" after "(h)" from "(i)" starting a list of clauses.
+    pub fn parse_after(text: &str, previous: &Designation) -> Option<Self> {
+        let candidates = candidates(text);
+        candidates
+            .iter()
+            .find(|candidate| candidate.kind == previous.kind)
+            .or(candidates.first())
+            .cloned()
+    }
+
+    /// The designation that would come next, as in "d" after "c", "101b"
+    /// after "101a" and "1395w-5" after "1395w-4". `None` when the number
+    /// to increment is already the largest there is.
+    pub fn next(&self) -> Option<Self> {
+        let mut suffix = self.suffix.clone();
+        let digits = suffix.len() - suffix.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0 {
+            let at = suffix.len() - digits;
+            let number = suffix[at..].parse::<u32>().ok()?.checked_add(1)?;
+            suffix.replace_range(at.., &number.to_string());
+        } else if let Some(last) = suffix.pop().filter(|c| c.is_ascii_alphabetic()) {
+            if last.eq_ignore_ascii_case(&'z') {
+                suffix.push(last);
+                suffix.push(if last.is_ascii_uppercase() { 'A' } else { 'a' });
+            } else {
+                suffix.push((last as u8 + 1) as char);
+            }
+        } else {
+            return Some(Designation::new(self.kind, self.value.checked_add(1)?));
+        }
+        Some(Designation {
+            kind: self.kind,
+            value: self.value,
+            suffix,
+        })
+    }
+
+    /// Whether the designation can directly follow `previous` in a list of
+    /// siblings: the next designation, the next value without a suffix, or
+    /// the first designation inserted after `previous`, as "101a" after
+    /// "101".
+    pub fn follows(&self, previous: &Designation) -> bool {
+        if self.kind != previous.kind {
+            return false;
+        }
+        previous.next().as_ref() == Some(self)
+            || (previous.value.checked_add(1) == Some(self.value) && self.suffix.is_empty())
+            || (self.value == previous.value
+                && previous.suffix.is_empty()
+                && ["a", "A", "-1"].contains(&self.suffix.as_str()))
+    }
+}
+
+impl FromStr for Designation {
+    type Err = String;
+
+    /// Parses the text of a `num`, as in "CHAPTER 1.", "§ 552." or "(iii)".
+    /// "i", "v" and "x" alone read as roman numerals, see
+    /// [`Designation::parse_after`] to read them in context.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        candidates(s)
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("`{s}` is not a designation"))
+    }
+}
+
+impl fmt::Display for Designation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DesignationKind::Arabic => write!(f, "{}", self.value)?,
+            DesignationKind::LowerAlpha => f.write_str(&alpha(self.value))?,
+            DesignationKind::UpperAlpha => f.write_str(&alpha(self.value).to_uppercase())?,
+            DesignationKind::LowerRoman => f.write_str(&roman(self.value))?,
+            DesignationKind::UpperRoman => f.write_str(&roman(self.value).to_uppercase())?,
+        }
+        f.write_str(&self.suffix)
+    }
+}
+
+impl Ord for Designation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value
+            .cmp(&other.value)
+            .then_with(|| segments(&self.suffix).cmp(&segments(&other.suffix)))
+            .then_with(|| self.kind.cmp(&other.kind))
+            // Suffixes with the same segments, as "w-04" and "w-4", are
+            // still different designations.
+            .then_with(|| self.suffix.cmp(&other.suffix))
+    }
+}
+
+impl PartialOrd for Designation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Tag<'_> {
+    /// The designation of a `num`, or of the `num` of a provision, from
+    /// its `value` when present and its text otherwise.
+    pub fn designation(&self) -> Option<Designation> {
+        let num = match self.tag_type {
+            TagType::Core(Core::Num) => self,
+            _ => self
+                .children
+                .iter()
+                .find(|child| child.tag_type == TagType::Core(Core::Num))?,
+        };
+        let value = num.attributes.iter().find_map(|attribute| match attribute {
+            Attribute::Value(value) => Some(*value),
+            _ => None,
+        });
+        match value {
+            Some(value) => value.parse().ok(),
+            None => num.text().parse().ok(),
+        }
+    }
+}
+
+/// The readings of a designation, the preferred one first.
+fn candidates(text: &str) -> Vec<Designation> {
+    // Keep the designation from "CHAPTER 1." or "§ 552." and drop the
+    // parentheses from "(a)".
+    let text = text.trim().trim_end_matches('.');
+    let text = text.rsplit(char::is_whitespace).next().unwrap_or(text);
+    let text = text.trim_start_matches('§');
+    let text = text
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+        .unwrap_or(text)
+        .replace(['–', '—'], "-");
+
+    let head = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if head > 0 {
+        let Ok(value) = text[..head].parse() else {
+            return Vec::new();
+        };
+        return vec![Designation {
+            kind: DesignationKind::Arabic,
+            value,
+            suffix: text[head..].to_string(),
+        }];
+    }
+
+    let head = text.len()
+        - text
+            .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .len();
+    let (letters, suffix) = text.split_at(head);
+    let upper = letters.chars().all(|c| c.is_ascii_uppercase());
+    if letters.is_empty() || !(upper || letters.chars().all(|c| c.is_ascii_lowercase())) {
+        return Vec::new();
+    }
+    let (alpha_kind, roman_kind) = if upper {
+        (DesignationKind::UpperAlpha, DesignationKind::UpperRoman)
+    } else {
+        (DesignationKind::LowerAlpha, DesignationKind::LowerRoman)
+    };
+    let lower = letters.to_ascii_lowercase();
+    let designation = |kind, value| Designation {
+        kind,
+        value,
+        suffix: suffix.to_string(),
+    };
+
+    let mut candidates = Vec::new();
+    if let Some(value) = from_roman(&lower) {
+        candidates.push(designation(roman_kind, value));
+    }
+    let first = lower.as_bytes()[0];
+    if lower.bytes().all(|c| c == first) {
+        let value = (lower.len() as u32 - 1) * 26 + u32::from(first - b'a') + 1;
+        let alpha = designation(alpha_kind, value);
+        // Only "i", "v" and "x" and their combinations are more likely
+        // roman numerals than letters.
+        if lower.chars().all(|c| matches!(c, 'i' | 'v' | 'x')) {
+            candidates.push(alpha);
+        } else {
+            candidates.insert(0, alpha);
+        }
+    }
+    candidates
+}
+
+/// "aa" for 27, as letters are doubled once the alphabet runs out.
+fn alpha(value: u32) -> String {
+    let index = (value.max(1) - 1) % 26;
+    let count = (value.max(1) - 1) / 26 + 1;
+    char::from(b'a' + index as u8)
+        .to_string()
+        .repeat(count as usize)
+}
+
+fn roman(mut value: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut out = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            out.push_str(numeral);
+            value -= amount;
+        }
+    }
+    out
+}
+
+/// The value of a lowercase roman numeral written in canonical form.
+fn from_roman(text: &str) -> Option<u32> {
+    let digit = |c| match c {
+        'i' => Some(1),
+        'v' => Some(5),
+        'x' => Some(10),
+        'l' => Some(50),
+        'c' => Some(100),
+        'd' => Some(500),
+        'm' => Some(1000),
+        _ => None,
+    };
+    let digits = text.chars().map(digit).collect::<Option<Vec<u32>>>()?;
+    let value = digits
+        .iter()
+        .enumerate()
+        .fold(0i64, |value, (index, digit)| match digits.get(index + 1) {
+            Some(next) if next > digit => value - *digit as i64,
+            _ => value + *digit as i64,
+        });
+    let value = u32::try_from(value).ok().filter(|value| *value > 0)?;
+    (roman(value) == text).then_some(value)
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment<'a> {
+    Number(u32),
+    Text(&'a str),
+}
+
+/// Splits a suffix into runs of digits and other characters, so that
+/// numbers compare by value.
+fn segments(suffix: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = suffix;
+    while let Some(first) = rest.chars().next() {
+        let digit = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(rest.len());
+        let (segment, tail) = rest.split_at(end);
+        segments.push(match segment.parse() {
+            Ok(number) if digit => Segment::Number(number),
+            _ => Segment::Text(segment),
+        });
+        rest = tail;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    use crate::Uslm;
+
+    fn parse(text: &str) -> (DesignationKind, u32, String) {
+        let designation: Designation = text.parse().unwrap();
+        (designation.kind, designation.value, designation.to_string())
+    }
+
+    #[test]
+    fn test_parse_designation() {
+        use DesignationKind::*;
+
+        assert_eq!(parse("CHAPTER 1."), (Arabic, 1, "1".to_string()));
+        assert_eq!(parse("§ 552."), (Arabic, 552, "552".to_string()));
+        assert_eq!(parse("(a)"), (LowerAlpha, 1, "a".to_string()));
+        assert_eq!(parse("(aa)"), (LowerAlpha, 27, "aa".to_string()));
+        assert_eq!(parse("(iii)"), (LowerRoman, 3, "iii".to_string()));
+        assert_eq!(parse("(II)"), (UpperRoman, 2, "II".to_string()));
+        assert_eq!(parse("(C)"), (UpperAlpha, 3, "C".to_string()));
+        assert_eq!(parse("TITLE XLII"), (UpperRoman, 42, "XLII".to_string()));
+        assert_eq!(parse("1395w–4"), (Arabic, 1395, "1395w-4".to_string()));
+        assert_eq!(parse("101a"), (Arabic, 101, "101a".to_string()));
+        assert!("(iiv)".parse::<Designation>().is_err());
+        assert!("(aB)".parse::<Designation>().is_err());
+    }
+
+    #[test]
+    fn test_parse_designation_after() {
+        let h: Designation = "(h)".parse().unwrap();
+        let iii: Designation = "(iii)".parse().unwrap();
+
+        let i = Designation::parse_after("(i)", &h).unwrap();
+        let iv = Designation::parse_after("(iv)", &iii).unwrap();
+
+        assert_eq!(i.kind, DesignationKind::LowerAlpha);
+        assert_eq!(i.value, 9);
+        assert!(i.follows(&h));
+        assert_eq!(iv.kind, DesignationKind::LowerRoman);
+        assert!(iv.follows(&iii));
+    }
+
+    #[test]
+    fn test_designation_order_and_next() {
+        let mut designations: Vec<Designation> = ["1395w-10", "101a", "1395w-4", "101", "2"]
+            .iter()
+            .map(|text| text.parse().unwrap())
+            .collect();
+
+        designations.sort();
+
+        assert_eq!(
+            designations
+                .iter()
+                .map(Designation::to_string)
+                .collect::<Vec<_>>(),
+            vec!["2", "101", "101a", "1395w-4", "1395w-10"]
+        );
+        let next = |designation: &Designation| designation.next().map(|next| next.to_string());
+        assert_eq!(next(&designations[2]), Some("101b".to_string()));
+        assert_eq!(next(&designations[3]), Some("1395w-5".to_string()));
+        assert_eq!(
+            next(&Designation::new(DesignationKind::LowerAlpha, 26)),
+            Some("aa".to_string())
+        );
+        assert_eq!(
+            next(&Designation::new(DesignationKind::UpperRoman, 8)),
+            Some("IX".to_string())
+        );
+        let last = Designation::new(DesignationKind::Arabic, u32::MAX);
+        assert_eq!(last.next(), None);
+        assert!(!last.follows(&last));
+        assert!(designations[2].follows(&designations[1]));
+        assert!(!designations[4].follows(&designations[2]));
+
+        let padded: Designation = "1395w-04".parse().unwrap();
+        assert_ne!(padded, designations[3]);
+        assert_ne!(padded.cmp(&designations[3]), Ordering::Equal);
+        let set: std::collections::BTreeSet<_> = [padded, designations[3].clone()].into();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_tag_designation() {
+        let mut input = r#"<bill><main><section><num value="552">§ 552.</num><subsection><num>(b)</num></subsection></section></main></bill>"#;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let section = &uslm.content[0].children[0].children[0];
+
+        assert_eq!(section.designation().unwrap().value, 552);
+        assert_eq!(
+            section.children[1].designation(),
+            Some(Designation::new(DesignationKind::LowerAlpha, 2))
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::HashMap;
+
+use crate::{
+    attributes::Attribute,
+    tags::{Core, Tag, TagType},
+    Uslm,
+};
+
+/// A difference between two versions of a document, at the level of
+/// provisions.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change<'a, 's> {
+    Inserted {
+        new: &'a Tag<'s>,
+    },
+    Deleted {
+        old: &'a Tag<'s>,
+    },
+    /// The provision now sits in a different enclosing provision.
+    Moved {
+        old: &'a Tag<'s>,
+        new: &'a Tag<'s>,
+    },
+    /// The `num` of the provision changed, as in "c." becoming "d.".
+    Renumbered {
+        old: &'a Tag<'s>,
+        new: &'a Tag<'s>,
+        from: String,
+        to: String,
+    },
+    /// The text of the provision itself changed, leaving out the text of
+    /// the provisions nested in it.
+    TextChanged {
+        old: &'a Tag<'s>,
+        new: &'a Tag<'s>,
+        words: Vec<WordDiff>,
+    },
+}
+
+/// A run of words in a text diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordDiff {
+    Equal(String),
+    Inserted(String),
+    Deleted(String),
+}
+
+/// Compares two versions of a document, aligning provisions by their
+/// `identifier`, then `id`, then the `num`s leading to them, rather than
+/// by position. Changes to provisions of `new` come first in document
+/// order, followed by the provisions deleted from `old`.
+pub fn diff<'a, 's>(old: &'a Uslm<'s>, new: &'a Uslm<'s>) -> Vec<Change<'a, 's>> {
+    let alignment = align(old, new);
+    let (old, new) = (&alignment.old, &alignment.new);
+
+    let mut changes = Vec::new();
+    for (index, provision) in new.iter().enumerate() {
+        let Some(counterpart) = alignment.pairs[index].map(|index| &old[index]) else {
+            changes.push(Change::Inserted { new: provision.tag });
+            continue;
+        };
+        if alignment.moved(index) {
+            changes.push(Change::Moved {
+                old: counterpart.tag,
+                new: provision.tag,
+            });
+        }
+        if counterpart.num != provision.num {
+            changes.push(Change::Renumbered {
+                old: counterpart.tag,
+                new: provision.tag,
+                from: counterpart.num.clone(),
+                to: provision.num.clone(),
+            });
+        }
+        if counterpart.text != provision.text {
+            changes.push(Change::TextChanged {
+                old: counterpart.tag,
+                new: provision.tag,
+                words: words(&counterpart.text, &provision.text),
+            });
+        }
+    }
+    changes.extend(alignment.deleted.iter().map(|index| Change::Deleted {
+        old: old[*index].tag,
+    }));
+    changes
+}
+
+/// The provisions of two versions of a document, paired up.
+pub(crate) struct Alignment<'a, 's> {
+    pub old: Vec<Provision<'a, 's>>,
+    pub new: Vec<Provision<'a, 's>>,
+    /// For each provision of `new`, the index of its counterpart in `old`.
+    pub pairs: Vec<Option<usize>>,
+    /// The provisions of `old` with no counterpart in `new`.
+    pub deleted: Vec<usize>,
+}
+
+impl Alignment<'_, '_> {
+    /// Whether the provision at `index` in `new` sits in a different
+    /// provision than its counterpart did.
+    pub fn moved(&self, index: usize) -> bool {
+        let Some(counterpart) = self.pairs[index] else {
+            return false;
+        };
+        let parent = self.new[index].parent.and_then(|parent| self.pairs[parent]);
+        parent != self.old[counterpart].parent
+    }
+}
+
+pub(crate) fn align<'a, 's>(old: &'a Uslm<'s>, new: &'a Uslm<'s>) -> Alignment<'a, 's> {
+    let old = provisions(old);
+    let new = provisions(new);
+    let old_keys: HashMap<&str, usize> = old
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, provision)| (provision.key.as_str(), index))
+        .collect();
+    let new_keys: HashMap<&str, usize> = new
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, provision)| (provision.key.as_str(), index))
+        .collect();
+
+    // Provisions whose key changed are paired up when their own text is
+    // unchanged, which is how renumbering without an `identifier` shows.
+    let mut deleted: Vec<usize> = (0..old.len())
+        .filter(|index| !new_keys.contains_key(old[*index].key.as_str()))
+        .collect();
+    let mut pairs: Vec<Option<usize>> = new
+        .iter()
+        .map(|provision| old_keys.get(provision.key.as_str()).copied())
+        .collect();
+    for (index, provision) in new.iter().enumerate() {
+        if pairs[index].is_some() || provision.text.is_empty() {
+            continue;
+        }
+        if let Some(position) = deleted
+            .iter()
+            .position(|candidate| old[*candidate].text == provision.text)
+        {
+            pairs[index] = Some(deleted.remove(position));
+        }
+    }
+
+    Alignment {
+        old,
+        new,
+        pairs,
+        deleted,
+    }
+}
+
+pub(crate) struct Provision<'a, 's> {
+    pub tag: &'a Tag<'s>,
+    key: String,
+    /// The index of the closest enclosing provision.
+    pub parent: Option<usize>,
+    num: String,
+    /// The text of the provision without its `num` and nested provisions.
+    pub text: String,
+}
+
+fn provisions<'a, 's>(uslm: &'a Uslm<'s>) -> Vec<Provision<'a, 's>> {
+    let mut provisions = Vec::new();
+    for tag in &uslm.content {
+        collect(tag, None, &mut provisions);
+    }
+    provisions
+}
+
+fn collect<'a, 's>(
+    tag: &'a Tag<'s>,
+    parent: Option<usize>,
+    provisions: &mut Vec<Provision<'a, 's>>,
+) {
+    if !is_provision(tag) {
+        for child in &tag.children {
+            collect(child, parent, provisions);
+        }
+        return;
+    }
+    let num = num(tag);
+    let parent_key = parent.map(|parent| provisions[parent].key.as_str());
+    let key = tag
+        .identifier()
+        .map(str::to_string)
+        .or_else(|| tag.id().map(|id| format!("#{id}")))
+        .unwrap_or_else(|| format!("{}/{num}", parent_key.unwrap_or_default()));
+    let index = provisions.len();
+    provisions.push(Provision {
+        tag,
+        key,
+        parent,
+        num,
+        text: own_text(tag),
+    });
+    for child in &tag.children {
+        collect(child, Some(index), provisions);
+    }
+}
+
+fn is_provision(tag: &Tag) -> bool {
+    matches!(tag.tag_type, TagType::Level(_) | TagType::Core(Core::Level))
+}
+
+/// The `value` of the provision's `num`, or its text when it has none.
+fn num(tag: &Tag) -> String {
+    let Some(num) = tag
+        .children
+        .iter()
+        .find(|child| child.tag_type == TagType::Core(Core::Num))
+    else {
+        return String::new();
+    };
+    num.attributes
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Value(value) => Some(value.to_string()),
+            _ => None,
+        })
+        .unwrap_or_else(|| num.text())
+}
+
+fn own_text(tag: &Tag) -> String {
+    let mut parts: Vec<String> = tag
+        .children
+        .iter()
+        .filter(|child| !is_provision(child) && child.tag_type != TagType::Core(Core::Num))
+        .map(Tag::text)
+        .filter(|text| !text.is_empty())
+        .collect();
+    if let Some(content) = &tag.content {
+        parts.extend(content.split_whitespace().map(str::to_string));
+    }
+    parts.join(" ")
+}
+
+/// Word-level diff of two texts from their longest common subsequence,
+/// with adjacent words of the same kind merged into one run and struck
+/// words ahead of the words replacing them. The words the texts start and
+/// end with in common are set aside first, and the rest is aligned in
+/// space linear in the length of the texts.
+pub(crate) fn words(old: &str, new: &str) -> Vec<WordDiff> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut edits = Vec::new();
+    align_words(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+        &mut edits,
+    );
+
+    let mut diff: Vec<WordDiff> = Vec::new();
+    let mut deleted: Vec<&str> = Vec::new();
+    let mut inserted: Vec<&str> = Vec::new();
+    let words = old[..prefix]
+        .iter()
+        .map(|word| Edit::Equal(word))
+        .chain(edits)
+        .chain(
+            old[old.len() - suffix..]
+                .iter()
+                .map(|word| Edit::Equal(word)),
+        );
+    for edit in words {
+        match edit {
+            Edit::Equal(word) => {
+                flush(&mut diff, &mut deleted, &mut inserted);
+                push(&mut diff, WordDiff::Equal(word.to_string()));
+            }
+            Edit::Deleted(word) => deleted.push(word),
+            Edit::Inserted(word) => inserted.push(word),
+        }
+    }
+    flush(&mut diff, &mut deleted, &mut inserted);
+    diff
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Edit<'a> {
+    Equal(&'a str),
+    Deleted(&'a str),
+    Inserted(&'a str),
+}
+
+/// Hirschberg's algorithm: splits `old` in half, finds where the longest
+/// common subsequence crosses `new` at that point, and aligns each side
+/// on its own.
+fn align_words<'a>(old: &[&'a str], new: &[&'a str], edits: &mut Vec<Edit<'a>>) {
+    match old {
+        [] => edits.extend(new.iter().map(|word| Edit::Inserted(word))),
+        _ if new.is_empty() => edits.extend(old.iter().map(|word| Edit::Deleted(word))),
+        [word] => match new.iter().position(|other| other == word) {
+            Some(at) => {
+                edits.extend(new[..at].iter().map(|word| Edit::Inserted(word)));
+                edits.push(Edit::Equal(word));
+                edits.extend(new[at + 1..].iter().map(|word| Edit::Inserted(word)));
+            }
+            None => {
+                edits.push(Edit::Deleted(word));
+                edits.extend(new.iter().map(|word| Edit::Inserted(word)));
+            }
+        },
+        _ => {
+            let middle = old.len() / 2;
+            let forward = lcs_lengths(&old[..middle], new);
+            let reversed = |words: &[&'a str]| words.iter().rev().copied().collect::<Vec<_>>();
+            let mut backward = lcs_lengths(&reversed(&old[middle..]), &reversed(new));
+            backward.reverse();
+            let split = (0..=new.len())
+                .max_by_key(|&j| (forward[j] + backward[j], std::cmp::Reverse(j)))
+                .unwrap_or(0);
+            align_words(&old[..middle], &new[..split], edits);
+            align_words(&old[middle..], &new[split..], edits);
+        }
+    }
+}
+
+/// The length of the longest common subsequence of `old` and each prefix
+/// of `new`, keeping a single row of the usual table.
+fn lcs_lengths(old: &[&str], new: &[&str]) -> Vec<usize> {
+    let mut previous = vec![0; new.len() + 1];
+    let mut current = vec![0; new.len() + 1];
+    for word in old {
+        for (j, other) in new.iter().enumerate() {
+            current[j + 1] = if word == other {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous
+}
+
+/// Appends the struck words, then the words replacing them.
+fn flush(diff: &mut Vec<WordDiff>, deleted: &mut Vec<&str>, inserted: &mut Vec<&str>) {
+    if !deleted.is_empty() {
+        push(diff, WordDiff::Deleted(deleted.join(" ")));
+        deleted.clear();
+    }
+    if !inserted.is_empty() {
+        push(diff, WordDiff::Inserted(inserted.join(" ")));
+        inserted.clear();
+    }
+}
+
+/// Appends `word`, merging it into the last run when of the same kind.
+fn push(diff: &mut Vec<WordDiff>, word: WordDiff) {
+    match (diff.last_mut(), &word) {
+        (Some(WordDiff::Equal(run)), WordDiff::Equal(next))
+        | (Some(WordDiff::Inserted(run)), WordDiff::Inserted(next))
+        | (Some(WordDiff::Deleted(run)), WordDiff::Deleted(next)) => {
+            run.push(' ');
+            run.push_str(next);
+        }
+        _ => diff.push(word),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn parse(input: &str) -> Uslm<'_> {
+        let mut input = input;
+        Uslm::parse(&mut input).unwrap()
+    }
+
+    #[test]
+    fn test_word_diff() {
+        let output = words(
+            "Each agency shall make available to the public",
+            "Each agency shall promptly make available to any person",
+        );
+
+        assert_eq!(
+            output,
+            vec![
+                WordDiff::Equal("Each agency shall".to_string()),
+                WordDiff::Inserted("promptly".to_string()),
+                WordDiff::Equal("make available to".to_string()),
+                WordDiff::Deleted("the public".to_string()),
+                WordDiff::Inserted("any person".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_diff_long_texts() {
+        let old: Vec<String> = (0..2000).map(|n| format!("w{n}")).collect();
+        let mut new = old.clone();
+        for n in (50..2000).step_by(100) {
+            new[n] = format!("x{n}");
+        }
+
+        let output = words(&old.join(" "), &new.join(" "));
+
+        let text = |keep: fn(&WordDiff) -> Option<&String>| {
+            output
+                .iter()
+                .filter_map(keep)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(
+            text(|word| match word {
+                WordDiff::Equal(text) | WordDiff::Deleted(text) => Some(text),
+                WordDiff::Inserted(_) => None,
+            }),
+            old.join(" ")
+        );
+        assert_eq!(
+            text(|word| match word {
+                WordDiff::Equal(text) | WordDiff::Inserted(text) => Some(text),
+                WordDiff::Deleted(_) => None,
+            }),
+            new.join(" ")
+        );
+        assert_eq!(output.len(), 20 * 3 + 1);
+        assert_eq!(output[1], WordDiff::Deleted("w50".to_string()));
+        assert_eq!(output[2], WordDiff::Inserted("x50".to_string()));
+    }
+
+    #[test]
+    fn test_diff_bill_stages() {
+        let old = parse(
+            r#"<bill><main>
+<section identifier="/us/bill/118/hr/1/s1"><num value="1">Sec. 1.</num><heading>Short title</heading></section>
+<section identifier="/us/bill/118/hr/1/s2"><num value="2">Sec. 2.</num><heading>Findings</heading></section>
+<section><num value="3">Sec. 3.</num><content>Funds are authorized to be appropriated</content></section>
+</main></bill>"#,
+        );
+        let new = parse(
+            r#"<bill><main>
+<section identifier="/us/bill/118/hr/1/s1"><num value="1">Sec. 1.</num><heading>Short title and table of contents</heading></section>
+<section><num value="2">Sec. 2.</num><content>Funds are authorized to be appropriated</content></section>
+<section identifier="/us/bill/118/hr/1/s3"><num value="3">Sec. 3.</num><heading>Effective date</heading></section>
+</main></bill>"#,
+        );
+
+        let output = diff(&old, &new);
+
+        let old_sections = &old.content[0].children[0].children;
+        let new_sections = &new.content[0].children[0].children;
+        assert_eq!(
+            output,
+            vec![
+                Change::TextChanged {
+                    old: &old_sections[0],
+                    new: &new_sections[0],
+                    words: vec![
+                        WordDiff::Equal("Short title".to_string()),
+                        WordDiff::Inserted("and table of contents".to_string()),
+                    ],
+                },
+                Change::Renumbered {
+                    old: &old_sections[2],
+                    new: &new_sections[1],
+                    from: "3".to_string(),
+                    to: "2".to_string(),
+                },
+                Change::Inserted {
+                    new: &new_sections[2]
+                },
+                Change::Deleted {
+                    old: &old_sections[1]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_moved_provision() {
+        let old = parse(
+            r#"<uscDoc><main><chapter identifier="/us/usc/t5/ch5"><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num></section></chapter><chapter identifier="/us/usc/t5/ch7"></chapter></main></uscDoc>"#,
+        );
+        let new = parse(
+            r#"<uscDoc><main><chapter identifier="/us/usc/t5/ch5"></chapter><chapter identifier="/us/usc/t5/ch7"><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num></section></chapter></main></uscDoc>"#,
+        );
+
+        let output = diff(&old, &new);
+
+        assert_eq!(
+            output,
+            vec![Change::Moved {
+                old: &old.content[0].children[0].children[0].children[0],
+                new: &new.content[0].children[0].children[1].children[0],
+            }]
+        );
+    }
+}
@@ -0,0 +1,512 @@
+//! Structural edits of a parsed document: inserting, removing, moving
+//! and redesignating provisions.
+//!
+//! Attribute values are `&'s str` slices of the source throughout the
+//! crate, which keeps parsing free of copies. Edits create values the
+//! source does not contain, such as recomputed identifiers, so they are
+//! stored in an [`Arena`] the caller keeps alive alongside the source.
+//! Switching the attributes to `Cow<'s, str>` instead would copy-on-write
+//! every attribute accessor and pattern in the crate for the sake of the
+//! few values edits create.
+
+use std::{
+    borrow::Cow,
+    cell::{Cell, OnceCell},
+    collections::HashMap,
+};
+
+use crate::{
+    amend::siblings_mut,
+    attributes::{kind, Attribute},
+    tags::{Core, Level, Tag, TagType},
+    Uslm,
+};
+
+/// Owns the attribute values created by edits, such as recomputed
+/// identifiers, which the edited document borrows just like the values of
+/// the source it was parsed from. Values live as long as the arena.
+#[derive(Debug, Default)]
+pub struct Arena {
+    chunks: Chunk,
+    len: Cell<usize>,
+}
+
+/// A fixed number of slots that never move once allocated, followed by a
+/// chunk twice as large once they are all taken.
+#[derive(Debug)]
+struct Chunk {
+    slots: Box<[OnceCell<Vec<String>>]>,
+    next: OnceCell<Box<Chunk>>,
+}
+
+impl Default for Chunk {
+    fn default() -> Self {
+        Chunk::with_capacity(8)
+    }
+}
+
+impl Chunk {
+    fn with_capacity(capacity: usize) -> Self {
+        Chunk {
+            slots: (0..capacity).map(|_| OnceCell::new()).collect(),
+            next: OnceCell::new(),
+        }
+    }
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Arena::default()
+    }
+
+    pub fn alloc(&self, value: impl Into<String>) -> &str {
+        &self.alloc_all(vec![value.into()])[0]
+    }
+
+    /// Stores `values` together. Chunks double in size, so finding the
+    /// free slot takes one step per chunk, logarithmic in earlier calls.
+    pub(crate) fn alloc_all(&self, values: Vec<String>) -> &[String] {
+        let mut chunk = &self.chunks;
+        let mut index = self.len.get();
+        while index >= chunk.slots.len() {
+            index -= chunk.slots.len();
+            let capacity = chunk.slots.len() * 2;
+            chunk = chunk
+                .next
+                .get_or_init(|| Box::new(Chunk::with_capacity(capacity)));
+        }
+        self.len.set(self.len.get() + 1);
+        chunk.slots[index].get_or_init(|| values)
+    }
+}
+
+impl<'s> Uslm<'s> {
+    /// Inserts `provision` right after the provision with `identifier`.
+    /// The provision and the levels inside it get identifiers built from
+    /// their `num`s under the same parent, replacing any they had.
+    pub fn insert_provision_after(
+        &mut self,
+        arena: &'s Arena,
+        identifier: &str,
+        mut provision: Tag<'s>,
+    ) -> Result<(), String> {
+        let (parent, _) = split(identifier)?;
+        let mut values = Vec::new();
+        identifiers(&provision, parent, &mut values);
+        if let Some(new) = values
+            .first()
+            .filter(|new| find(&self.content, new).is_some())
+        {
+            return Err(exists(new));
+        }
+        let (siblings, index) =
+            siblings_mut(&mut self.content, identifier).ok_or_else(|| missing(identifier))?;
+        let values = arena.alloc_all(values);
+        assign_identifiers(&mut provision, &mut values.iter());
+        siblings.insert(index + 1, provision);
+        Ok(())
+    }
+
+    /// Removes the provision with `identifier` and returns it. References
+    /// to it are left as they are.
+    pub fn remove_provision(&mut self, identifier: &str) -> Result<Tag<'s>, String> {
+        let (siblings, index) =
+            siblings_mut(&mut self.content, identifier).ok_or_else(|| missing(identifier))?;
+        Ok(siblings.remove(index))
+    }
+
+    /// Moves the provision with `identifier` right after the provision
+    /// with identifier `after`, keeping its designation. The identifiers
+    /// under its new parent replace the old ones, in the provision and in
+    /// every `ref` pointing into it.
+    pub fn move_provision(
+        &mut self,
+        arena: &'s Arena,
+        identifier: &str,
+        after: &str,
+    ) -> Result<(), String> {
+        let (_, segment) = split(identifier)?;
+        let (parent, _) = split(after)?;
+        let provision = find(&self.content, identifier).ok_or_else(|| missing(identifier))?;
+        if find(std::slice::from_ref(provision), after).is_some() {
+            return Err(format!("`{identifier}` cannot be moved into itself"));
+        }
+        if find(&self.content, after).is_none() {
+            return Err(missing(after));
+        }
+        let moved = format!("{parent}/{segment}");
+        if moved != identifier && find(&self.content, &moved).is_some() {
+            return Err(exists(&moved));
+        }
+
+        let provision = self.remove_provision(identifier)?;
+        let (siblings, index) =
+            siblings_mut(&mut self.content, after).ok_or_else(|| missing(after))?;
+        siblings.insert(index + 1, provision);
+        self.rename(arena, identifier, &moved);
+        Ok(())
+    }
+
+    /// Gives the provision with `identifier` a new designation, as in "c"
+    /// for "(b)", updating the text and `value` of its `num`, the
+    /// identifiers of the provision and the levels inside it, and every
+    /// `ref` pointing into it. `id`s stay as they are, so `idref`s keep
+    /// pointing at the provision.
+    pub fn redesignate(
+        &mut self,
+        arena: &'s Arena,
+        identifier: &str,
+        designation: &str,
+    ) -> Result<(), String> {
+        let (parent, segment) = split(identifier)?;
+        let tag = find(&self.content, identifier).ok_or_else(|| missing(identifier))?;
+        let TagType::Level(level) = tag.tag_type else {
+            return Err(format!("`{identifier}` is not a level"));
+        };
+        let at = tag
+            .children
+            .iter()
+            .position(|child| child.tag_type == TagType::Core(Core::Num))
+            .ok_or_else(|| format!("`{identifier}` has no `num` to redesignate"))?;
+        let num = &tag.children[at];
+        let old = value(num)
+            .map(str::to_string)
+            .or_else(|| num.designation().map(|designation| designation.to_string()))
+            .ok_or_else(|| format!("`{identifier}` has no designation"))?;
+        // Keep the prefix of the old identifier, as the "s" of "s552".
+        let prefix = segment.strip_suffix(old.as_str()).unwrap_or(prefix(level));
+        let renamed = format!("{parent}/{prefix}{designation}");
+        if renamed != identifier && find(&self.content, &renamed).is_some() {
+            return Err(exists(&renamed));
+        }
+
+        let (siblings, index) =
+            siblings_mut(&mut self.content, identifier).ok_or_else(|| missing(identifier))?;
+        let num = &mut siblings[index].children[at];
+        if let Some(content) = &mut num.content {
+            if let Some(at) = content.rfind(&old) {
+                let mut text = content.to_string();
+                text.replace_range(at..at + old.len(), designation);
+                *content = Cow::Owned(text);
+            }
+        }
+        num.set_attr(Attribute::Value(arena.alloc(designation)));
+        self.rename(arena, identifier, &renamed);
+        Ok(())
+    }
+
+    /// Replaces `from` with `to` in identifiers and `href`s, for `from`
+    /// itself and everything under it.
+    fn rename(&mut self, arena: &'s Arena, from: &str, to: &str) {
+        let mut renames: Vec<(&'s str, String)> = Vec::new();
+        collect_renames(&self.content, from, to, &mut renames);
+        renames.sort();
+        renames.dedup();
+        let values = arena.alloc_all(renames.iter().map(|(_, new)| new.clone()).collect());
+        let renames: HashMap<&str, &'s str> = renames
+            .iter()
+            .map(|(old, _)| *old)
+            .zip(values.iter().map(String::as_str))
+            .collect();
+        apply_renames(&mut self.content, &renames);
+    }
+}
+
+fn missing(identifier: &str) -> String {
+    format!("`{identifier}` does not exist")
+}
+
+fn exists(identifier: &str) -> String {
+    format!("`{identifier}` already exists")
+}
+
+/// `/us/usc/t5/s552/a` into `/us/usc/t5/s552` and `a`.
+fn split(identifier: &str) -> Result<(&str, &str), String> {
+    identifier
+        .rsplit_once('/')
+        .filter(|(parent, segment)| !parent.is_empty() && !segment.is_empty())
+        .ok_or_else(|| format!("`{identifier}` is not an absolute identifier"))
+}
+
+fn find<'a, 's>(tags: &'a [Tag<'s>], identifier: &str) -> Option<&'a Tag<'s>> {
+    tags.iter().find_map(|tag| {
+        let found = tag.attributes.iter().any(
+            |attribute| matches!(attribute, Attribute::Identifier(value) if *value == identifier),
+        );
+        if found {
+            Some(tag)
+        } else {
+            find(&tag.children, identifier)
+        }
+    })
+}
+
+fn value<'s>(num: &Tag<'s>) -> Option<&'s str> {
+    num.attributes.iter().find_map(|attribute| match attribute {
+        Attribute::Value(value) => Some(*value),
+        _ => None,
+    })
+}
+
+/// The identifier prefix of a level, as in `t5` for title 5 and `s552`
+/// for section 552. Levels below the section use their designation alone.
+pub(crate) fn prefix(level: Level) -> &'static str {
+    match level {
+        Level::Title => "t",
+        Level::Subtitle => "st",
+        Level::Chapter => "ch",
+        Level::Subchapter => "sch",
+        Level::Part => "pt",
+        Level::Subpart => "spt",
+        Level::Division => "d",
+        Level::Subdivision => "sd",
+        Level::Article => "art",
+        Level::Subarticle => "sart",
+        Level::Section => "s",
+        _ => "",
+    }
+}
+
+/// The identifiers of the levels in `tag`, in document order, built from
+/// their `num`s under `parent`. Levels without a `num` get none.
+fn identifiers(tag: &Tag, parent: &str, values: &mut Vec<String>) {
+    let mut parent = parent.to_string();
+    if let TagType::Level(level) = tag.tag_type {
+        let num = tag
+            .children
+            .iter()
+            .find(|child| child.tag_type == TagType::Core(Core::Num));
+        let designation = num.and_then(|num| {
+            value(num)
+                .map(str::to_string)
+                .or_else(|| num.designation().map(|designation| designation.to_string()))
+        });
+        if let Some(designation) = designation {
+            parent = format!("{parent}/{}{designation}", prefix(level));
+            values.push(parent.clone());
+        }
+    }
+    for child in &tag.children {
+        identifiers(child, &parent, values);
+    }
+}
+
+/// Sets the identifiers computed by [`identifiers`], in the same order.
+fn assign_identifiers<'s>(tag: &mut Tag<'s>, values: &mut std::slice::Iter<'s, String>) {
+    let numbered = matches!(tag.tag_type, TagType::Level(_))
+        && tag.children.iter().any(|child| {
+            child.tag_type == TagType::Core(Core::Num)
+                && (value(child).is_some() || child.designation().is_some())
+        });
+    if numbered {
+        if let Some(identifier) = values.next() {
+            tag.set_identifier(identifier);
+        }
+    }
+    for child in &mut tag.children {
+        assign_identifiers(child, values);
+    }
+}
+
+fn collect_renames<'s>(
+    tags: &[Tag<'s>],
+    from: &str,
+    to: &str,
+    renames: &mut Vec<(&'s str, String)>,
+) {
+    for tag in tags {
+        for attribute in &tag.attributes {
+            if let Attribute::Identifier(value) | Attribute::Href(value) = attribute {
+                let rest = value
+                    .strip_prefix(from)
+                    .filter(|rest| rest.is_empty() || rest.starts_with('/'));
+                if let Some(rest) = rest {
+                    renames.push((value, format!("{to}{rest}")));
+                }
+            }
+        }
+        collect_renames(&tag.children, from, to, renames);
+    }
+}
+
+fn apply_renames<'s>(tags: &mut [Tag<'s>], renames: &HashMap<&str, &'s str>) {
+    for tag in tags {
+        let rename = |value: Option<&mut &'s str>| {
+            if let Some(value) = value {
+                if let Some(new) = renames.get(value) {
+                    *value = new;
+                }
+            }
+        };
+        rename(tag.attr_mut::<kind::Identifier>());
+        rename(tag.attr_mut::<kind::Href>());
+        apply_renames(&mut tag.children, renames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<section identifier="/us/usc/t5/s552">
+<num value="552">Sec. 552.</num>
+<subsection identifier="/us/usc/t5/s552/a"><num value="a">(a)</num><content>Each agency</content></subsection>
+<subsection identifier="/us/usc/t5/s552/b">
+<num value="b">(b)</num>
+<paragraph identifier="/us/usc/t5/s552/b/1"><num value="1">(1)</num><content>specifically authorized</content></paragraph>
+</subsection>
+</section>
+<section identifier="/us/usc/t5/s553">
+<num value="553">Sec. 553.</num>
+<content><ref href="/us/usc/t5/s552/b/1">section 552(b)(1)</ref></content>
+</section>
+</main>
+</uscDoc>"#;
+
+    /// Identifiers and `href`s of the document, in document order.
+    fn pointers<'s>(tags: &[Tag<'s>], out: &mut Vec<&'s str>) {
+        for tag in tags {
+            for attribute in &tag.attributes {
+                if let Attribute::Identifier(value) | Attribute::Href(value) = attribute {
+                    out.push(value);
+                }
+            }
+            pointers(&tag.children, out);
+        }
+    }
+
+    fn all_pointers<'s>(uslm: &Uslm<'s>) -> Vec<&'s str> {
+        let mut out = Vec::new();
+        pointers(&uslm.content, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_arena_keeps_values() {
+        let arena = Arena::new();
+
+        let values: Vec<&str> = (0..100).map(|n| arena.alloc(n.to_string())).collect();
+        let pair = arena.alloc_all(vec!["a".to_string(), "b".to_string()]);
+
+        assert_eq!(values[0], "0");
+        assert_eq!(values[8], "8");
+        assert_eq!(values[99], "99");
+        assert_eq!(pair, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_redesignate() {
+        let mut input = INPUT;
+        let mut uslm = Uslm::parse(&mut input).unwrap();
+        let arena = Arena::new();
+
+        uslm.redesignate(&arena, "/us/usc/t5/s552/b", "c").unwrap();
+
+        let num = &uslm.content[0].children[0].children[0].children[2].children[0];
+        assert_eq!(num.content.as_deref(), Some("(c)"));
+        assert_eq!(num.attributes, vec![Attribute::Value("c")]);
+        assert_eq!(
+            all_pointers(&uslm),
+            vec![
+                "/us/usc/t5",
+                "/us/usc/t5/s552",
+                "/us/usc/t5/s552/a",
+                "/us/usc/t5/s552/c",
+                "/us/usc/t5/s552/c/1",
+                "/us/usc/t5/s553",
+                "/us/usc/t5/s552/c/1",
+            ]
+        );
+
+        uslm.redesignate(&arena, "/us/usc/t5/s553", "554").unwrap();
+        let section = &uslm.content[0].children[0].children[1];
+        assert_eq!(section.children[0].content.as_deref(), Some("Sec. 554."));
+        assert_eq!(
+            section.attributes,
+            vec![Attribute::Identifier("/us/usc/t5/s554")]
+        );
+
+        assert_eq!(
+            uslm.redesignate(&arena, "/us/usc/t5/s552/a", "c"),
+            Err("`/us/usc/t5/s552/c` already exists".to_string())
+        );
+        let num = &uslm.content[0].children[0].children[0].children[1].children[0];
+        assert_eq!(num.attributes, vec![Attribute::Value("a")]);
+    }
+
+    #[test]
+    fn test_move_and_remove_provision() {
+        let arena = Arena::new();
+        let mut input = INPUT;
+        let mut uslm = Uslm::parse(&mut input).unwrap();
+
+        uslm.move_provision(&arena, "/us/usc/t5/s552/b/1", "/us/usc/t5/s552/a")
+            .unwrap();
+
+        assert_eq!(
+            all_pointers(&uslm),
+            vec![
+                "/us/usc/t5",
+                "/us/usc/t5/s552",
+                "/us/usc/t5/s552/a",
+                "/us/usc/t5/s552/1",
+                "/us/usc/t5/s552/b",
+                "/us/usc/t5/s553",
+                "/us/usc/t5/s552/1",
+            ]
+        );
+        assert_eq!(
+            uslm.move_provision(&arena, "/us/usc/t5/s552", "/us/usc/t5/s552/a"),
+            Err("`/us/usc/t5/s552` cannot be moved into itself".to_string())
+        );
+        let mut other = r#"<uscDoc identifier="/us/usc/t5"><main>
+<section identifier="/us/usc/t5/s552"><subsection identifier="/us/usc/t5/s552/a"><num value="a">(a)</num></subsection></section>
+<section identifier="/us/usc/t5/s553"><subsection identifier="/us/usc/t5/s553/a"><num value="a">(a)</num></subsection><subsection identifier="/us/usc/t5/s553/b"><num value="b">(b)</num></subsection></section>
+</main></uscDoc>"#;
+        let mut other = Uslm::parse(&mut other).unwrap();
+        assert_eq!(
+            other.move_provision(&arena, "/us/usc/t5/s552/a", "/us/usc/t5/s553/b"),
+            Err("`/us/usc/t5/s553/a` already exists".to_string())
+        );
+
+        let removed = uslm.remove_provision("/us/usc/t5/s552/1").unwrap();
+        assert_eq!(removed.text(), "(1) specifically authorized");
+        assert_eq!(
+            uslm.remove_provision("/us/usc/t5/s552/1"),
+            Err("`/us/usc/t5/s552/1` does not exist".to_string())
+        );
+    }
+
+    #[test]
+    fn test_insert_provision_after() {
+        let arena = Arena::new();
+        let mut input = INPUT;
+        let mut uslm = Uslm::parse(&mut input).unwrap();
+        let mut fragment = r#"<subsection><num>(c)</num><paragraph><num value="1">(1)</num><content>new text</content></paragraph></subsection>"#;
+        let provision = Uslm::parse(&mut fragment).unwrap().content.remove(0);
+
+        uslm.insert_provision_after(&arena, "/us/usc/t5/s552/b", provision)
+            .unwrap();
+
+        let section = &uslm.content[0].children[0].children[0];
+        assert_eq!(section.children.len(), 4);
+        assert_eq!(
+            section.children[3].attributes,
+            vec![Attribute::Identifier("/us/usc/t5/s552/c")]
+        );
+        assert_eq!(
+            section.children[3].children[1].attributes,
+            vec![Attribute::Identifier("/us/usc/t5/s552/c/1")]
+        );
+        let mut fragment = r#"<subsection><num value="a">(a)</num></subsection>"#;
+        let provision = Uslm::parse(&mut fragment).unwrap().content.remove(0);
+        assert_eq!(
+            uslm.insert_provision_after(&arena, "/us/usc/t5/s552/b", provision),
+            Err("`/us/usc/t5/s552/a` already exists".to_string())
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{
+    span::Span,
+    tags::{Note, Tag, TagType},
+    temporal::Date,
+    Uslm,
+};
+
+/// One law in the enactment history of a provision, as cited in its
+/// `sourceCredit`, such as "Pub. L. 104–231, §3, Oct. 2, 1996, 110 Stat.
+/// 3049". Older credits cite a chapter of the Statutes at Large instead
+/// of a Public Law.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enactment {
+    pub public_law: Option<PublicLaw>,
+    /// The section of the law, as in "3" or "101(a)".
+    pub section: Option<String>,
+    pub date: Option<Date>,
+    pub statutes_at_large: Option<StatutesAtLarge>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PublicLaw {
+    pub congress: u16,
+    pub number: u32,
+}
+
+impl PublicLaw {
+    /// The USLM reference of the law, as in `/us/pl/89/554`.
+    pub fn href(&self) -> String {
+        format!("/us/pl/{}/{}", self.congress, self.number)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StatutesAtLarge {
+    pub volume: u16,
+    pub page: u32,
+}
+
+impl StatutesAtLarge {
+    /// The USLM reference of the page, as in `/us/stat/80/383`.
+    pub fn href(&self) -> String {
+        format!("/us/stat/{}/{}", self.volume, self.page)
+    }
+}
+
+/// The enactment history of one provision, see [`Uslm::source_credits`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct SourceCredit<'s> {
+    /// The `identifier` of the provision the `sourceCredit` belongs to.
+    pub provision: Option<&'s str>,
+    pub enactments: Vec<Enactment>,
+    pub span: Span,
+}
+
+impl<'s> Uslm<'s> {
+    /// The parsed `sourceCredit` of every provision that has one, in
+    /// document order.
+    pub fn source_credits(&self) -> Vec<SourceCredit<'s>> {
+        let mut credits = Vec::new();
+        for tag in &self.content {
+            collect(tag, None, &mut credits);
+        }
+        credits
+    }
+}
+
+fn collect<'s>(tag: &Tag<'s>, provision: Option<&'s str>, credits: &mut Vec<SourceCredit<'s>>) {
+    if tag.tag_type == TagType::Note(Note::SourceCredit) {
+        credits.push(SourceCredit {
+            provision,
+            enactments: enactments(&tag.text()),
+            span: tag.span(),
+        });
+        return;
+    }
+    let provision = match tag.tag_type {
+        TagType::Level(_) => tag.identifier().or(provision),
+        _ => provision,
+    };
+    for child in &tag.children {
+        collect(child, provision, credits);
+    }
+}
+
+/// Parses the text of a `sourceCredit`, one enactment per law separated
+/// by semicolons. Parts that are not recognized, such as "title I" or
+/// "ch. 645", are skipped.
+pub fn enactments(text: &str) -> Vec<Enactment> {
+    let text = text
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(['.', ')']);
+    text.split(';')
+        .filter_map(|entry| {
+            let mut enactment = Enactment {
+                public_law: None,
+                section: None,
+                date: None,
+                statutes_at_large: None,
+            };
+            let parts: Vec<&str> = entry.split(',').map(str::trim).collect();
+            let mut parts = parts.iter().peekable();
+            while let Some(part) = parts.next() {
+                if let Some(law) = part.strip_prefix("Pub. L.") {
+                    enactment.public_law = public_law(law.trim());
+                } else if let Some(section) = part.strip_prefix('§') {
+                    enactment.section = Some(section.trim_start_matches('§').trim().to_string());
+                } else if let Some(statutes) = statutes_at_large(part) {
+                    enactment.statutes_at_large = Some(statutes);
+                } else if let Some((month, day)) = month_day(part) {
+                    // Dates are written "Sept. 6, 1966", so the year is the
+                    // next part. The day is checked against the month once
+                    // the year is known, leaving out dates as "Feb. 30".
+                    let year = parts.peek().and_then(|year| year.parse().ok());
+                    if let Some(year) = year {
+                        parts.next();
+                        enactment.date = Date::new(year, month, day);
+                    }
+                }
+            }
+            let empty = enactment.public_law.is_none()
+                && enactment.date.is_none()
+                && enactment.statutes_at_large.is_none();
+            (!empty).then_some(enactment)
+        })
+        .collect()
+}
+
+/// "89–554", with an en dash or a hyphen.
+fn public_law(text: &str) -> Option<PublicLaw> {
+    let (congress, number) = text.split_once(['–', '-'])?;
+    Some(PublicLaw {
+        congress: congress.parse().ok()?,
+        number: number.parse().ok()?,
+    })
+}
+
+/// "80 Stat. 383".
+fn statutes_at_large(text: &str) -> Option<StatutesAtLarge> {
+    let (volume, page) = text.split_once(" Stat. ")?;
+    Some(StatutesAtLarge {
+        volume: volume.parse().ok()?,
+        page: page.parse().ok()?,
+    })
+}
+
+/// "Sept. 6", with the month abbreviated as in the United States Code.
+/// Whether the day exists in that month is left to [`Date::new`].
+fn month_day(text: &str) -> Option<(u8, u8)> {
+    const MONTHS: [&str; 12] = [
+        "Jan.", "Feb.", "Mar.", "Apr.", "May", "June", "July", "Aug.", "Sept.", "Oct.", "Nov.",
+        "Dec.",
+    ];
+    let (month, day) = text.split_once(' ')?;
+    let month = MONTHS.iter().position(|name| *name == month)?;
+    let day = day.parse().ok().filter(|day| (1..=31).contains(day))?;
+    Some((month as u8 + 1, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_enactments() {
+        let output = enactments(
+            "(Pub. L. 89–554, Sept. 6, 1966, 80 Stat. 383; Pub. L. 104–231, §3, Oct. 2, 1996, 110 Stat. 3049.)",
+        );
+
+        assert_eq!(
+            output,
+            vec![
+                Enactment {
+                    public_law: Some(PublicLaw {
+                        congress: 89,
+                        number: 554
+                    }),
+                    section: None,
+                    date: Date::new(1966, 9, 6),
+                    statutes_at_large: Some(StatutesAtLarge {
+                        volume: 80,
+                        page: 383
+                    }),
+                },
+                Enactment {
+                    public_law: Some(PublicLaw {
+                        congress: 104,
+                        number: 231
+                    }),
+                    section: Some("3".to_string()),
+                    date: Date::new(1996, 10, 2),
+                    statutes_at_large: Some(StatutesAtLarge {
+                        volume: 110,
+                        page: 3049
+                    }),
+                },
+            ]
+        );
+        assert_eq!(output[0].public_law.unwrap().href(), "/us/pl/89/554");
+    }
+
+    #[test]
+    fn test_enactments_without_public_law() {
+        let output = enactments("(June 25, 1948, ch. 645, 62 Stat. 683; Pub. L. 94–550, title I, §101(a), Oct. 18, 1976, 90 Stat. 2530.)");
+
+        assert_eq!(
+            output
+                .iter()
+                .map(|enactment| (
+                    enactment.public_law,
+                    enactment.section.as_deref(),
+                    enactment.date
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, None, Date::new(1948, 6, 25)),
+                (
+                    Some(PublicLaw {
+                        congress: 94,
+                        number: 550
+                    }),
+                    Some("101(a)"),
+                    Date::new(1976, 10, 18)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enactments_impossible_date() {
+        let output = enactments("(Pub. L. 101–508, Feb. 30, 1990, 104 Stat. 1388.)");
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].date, None);
+        assert_eq!(
+            output[0].statutes_at_large,
+            Some(StatutesAtLarge {
+                volume: 104,
+                page: 1388
+            })
+        );
+    }
+
+    #[test]
+    fn test_source_credits() {
+        let mut input = r#"<uscDoc><main><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num><sourceCredit>(Pub. L. 89–554, Sept. 6, 1966, 80 Stat. 383.)</sourceCredit></section></main></uscDoc>"#;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.source_credits();
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].provision, Some("/us/usc/t5/s552"));
+        assert_eq!(
+            output[0].enactments[0].statutes_at_large.unwrap().href(),
+            "/us/stat/80/383"
+        );
+    }
+}
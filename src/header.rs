@@ -1,36 +1,246 @@
 use std::{error::Error, str::FromStr};
 use winnow::{
-    combinator::{alt, delimited, repeat},
-    token::literal,
+    ascii::{multispace0, multispace1},
+    combinator::{alt, cut_err, delimited, opt, preceded, repeat, separated_pair, terminated},
+    error::{ContextError, ErrMode},
+    token::{literal, take_till, take_until, take_while},
     ModalResult, Parser,
 };
 
 use crate::{
-    attributes::{Attribute, VecExt},
-    common::kvs,
+    attributes::{Attribute, Encoding, Version},
+    common::{invalid, kvs},
 };
 
-#[derive(Debug, PartialEq, Eq)]
+/// Everything before the root element: the XML declaration, processing
+/// instructions such as `<?xml-stylesheet?>` and the document type
+/// declaration. A byte order mark, whitespace and comments around them are
+/// skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Header<'s> {
+    pub declaration: Option<XmlDeclaration>,
     pub tags: Vec<HeaderTag<'s>>,
+    pub doctype: Option<Doctype<'s>>,
 }
 
 impl<'s> Header<'s> {
     pub(super) fn parse(input: &mut &'s str) -> ModalResult<Self> {
-        let tags = repeat(0.., delimited("<?", header_tag, "?>")).parse_next(input)?;
-        Ok(Header { tags })
+        opt('\u{feff}').parse_next(input)?;
+        // The declaration belongs at the very start, but whitespace left
+        // before it by editors and templates is tolerated.
+        multispace0.parse_next(input)?;
+        let declaration = opt(XmlDeclaration::parse).parse_next(input)?;
+
+        let mut header = Header {
+            declaration,
+            ..Header::default()
+        };
+        loop {
+            misc(input)?;
+            if header.doctype.is_none() {
+                if let Some(doctype) = opt(Doctype::parse).parse_next(input)? {
+                    header.doctype = Some(doctype);
+                    continue;
+                }
+            }
+            match opt(delimited("<?", header_tag, "?>")).parse_next(input)? {
+                Some(tag) => header.tags.push(tag),
+                None => break,
+            }
+        }
+        Ok(header)
+    }
+}
+
+/// The `<?xml?>` declaration opening a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlDeclaration {
+    pub version: Version,
+    pub encoding: Option<Encoding>,
+    /// Whether the document declares itself free of external markup
+    /// declarations, `standalone="yes"`.
+    pub standalone: Option<bool>,
+}
+
+impl XmlDeclaration {
+    /// The `version`, then optionally `encoding` and `standalone`, in that
+    /// order. A malformed declaration is an error rather than something to
+    /// backtrack from.
+    fn parse(input: &mut &str) -> ModalResult<Self> {
+        let pairs: Vec<(&str, &str)> = preceded(
+            ("<?xml", multispace1),
+            cut_err(terminated(
+                repeat(1.., terminated(pseudo_attribute, multispace0)),
+                "?>",
+            )),
+        )
+        .parse_next(input)?;
+        XmlDeclaration::from_pairs(&pairs).map_err(|error| invalid(input, error))
+    }
+
+    fn from_pairs(pairs: &[(&str, &str)]) -> Result<Self, Box<dyn Error>> {
+        let mut pairs = pairs.iter().copied().peekable();
+        let version = match pairs.next() {
+            Some(("version", version)) => version.parse()?,
+            _ => return Err("the XML declaration must start with `version`".into()),
+        };
+        let encoding = pairs
+            .next_if(|(key, _)| *key == "encoding")
+            .map(|(_, encoding)| encoding.parse())
+            .transpose()?;
+        let standalone = match pairs.next_if(|(key, _)| *key == "standalone") {
+            Some((_, "yes")) => Some(true),
+            Some((_, "no")) => Some(false),
+            Some((_, value)) => return Err(format!("invalid `standalone` value `{value}`").into()),
+            None => None,
+        };
+        if let Some((key, _)) = pairs.next() {
+            return Err(format!("unexpected `{key}` in the XML declaration").into());
+        }
+        Ok(XmlDeclaration {
+            version,
+            encoding,
+            standalone,
+        })
+    }
+}
+
+/// A `<!DOCTYPE>` declaration. The internal subset is kept as written,
+/// its entities are not expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Doctype<'s> {
+    /// The name of the root element.
+    pub name: &'s str,
+    pub external_id: Option<ExternalId<'s>>,
+    /// The markup declarations between `[` and `]`.
+    pub internal_subset: Option<&'s str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalId<'s> {
+    System(&'s str),
+    Public {
+        public_id: &'s str,
+        system_id: &'s str,
+    },
+}
+
+impl<'s> Doctype<'s> {
+    fn parse(input: &mut &'s str) -> ModalResult<Self> {
+        let (name, external_id, internal_subset) = preceded(
+            ("<!DOCTYPE", multispace1),
+            cut_err(terminated(
+                (
+                    name,
+                    opt(preceded(multispace1, external_id)),
+                    preceded(
+                        multispace0,
+                        opt(terminated(
+                            delimited('[', internal_subset, ']'),
+                            multispace0,
+                        )),
+                    ),
+                ),
+                '>',
+            )),
+        )
+        .parse_next(input)?;
+        Ok(Doctype {
+            name,
+            external_id,
+            internal_subset,
+        })
+    }
+}
+
+fn external_id<'s>(input: &mut &'s str) -> ModalResult<ExternalId<'s>> {
+    alt((
+        preceded(("SYSTEM", multispace1), quoted).map(ExternalId::System),
+        preceded(
+            ("PUBLIC", multispace1),
+            separated_pair(quoted, multispace1, quoted),
+        )
+        .map(|(public_id, system_id)| ExternalId::Public {
+            public_id,
+            system_id,
+        }),
+    ))
+    .parse_next(input)
+}
+
+/// Everything up to the `]` closing the internal subset, skipping over
+/// brackets in quoted literals and comments.
+fn internal_subset<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+    let mut rest = *input;
+    loop {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or(ErrMode::Backtrack(ContextError::new()))?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some(']') => break,
+            Some(quote @ ('"' | '\'')) => {
+                let end = chars
+                    .as_str()
+                    .find(quote)
+                    .ok_or(ErrMode::Backtrack(ContextError::new()))?;
+                rest = &chars.as_str()[end + 1..];
+            }
+            Some(_) => rest = chars.as_str(),
+            None => return Err(ErrMode::Backtrack(ContextError::new())),
+        }
     }
+    let length = input.len() - rest.len();
+    let subset = &input[..length];
+    *input = rest;
+    Ok(subset)
+}
+
+/// Whitespace and comments, which may appear anywhere between the parts
+/// of the header.
+fn misc(input: &mut &str) -> ModalResult<()> {
+    repeat(
+        0..,
+        alt((
+            multispace1.void(),
+            delimited("<!--", take_until(0.., "-->"), "-->").void(),
+        )),
+    )
+    .parse_next(input)
+}
+
+/// `key="value"` or `key='value'`, with optional whitespace around `=`.
+fn pseudo_attribute<'s>(input: &mut &'s str) -> ModalResult<(&'s str, &'s str)> {
+    separated_pair(name, (multispace0, '=', multispace0), quoted).parse_next(input)
+}
+
+fn name<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+    take_while(1.., |c: char| {
+        c.is_alphanumeric() || matches!(c, ':' | '_' | '-' | '.')
+    })
+    .parse_next(input)
 }
 
-#[derive(Debug, PartialEq, Eq)]
+fn quoted<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+    alt((
+        delimited('"', take_till(0.., '"'), '"'),
+        delimited('\'', take_till(0.., '\''), '\''),
+    ))
+    .parse_next(input)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HeaderTag<'s> {
     pub tag_type: HeaderTagType,
     pub attributes: Vec<Attribute<'s>>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HeaderTagType {
-    Xml,
     XmlStyleSheet,
 }
 
@@ -39,7 +249,6 @@ impl FromStr for HeaderTagType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let item = match s {
-            "xml" => HeaderTagType::Xml,
             "xml-stylesheet" => HeaderTagType::XmlStyleSheet,
             _ => panic!("Unknown HeaderTagType: {:#?}", s),
         };
@@ -50,15 +259,21 @@ impl FromStr for HeaderTagType {
 fn header_tag<'s>(input: &mut &'s str) -> ModalResult<HeaderTag<'s>> {
     let tag_type = HeaderTagType::from_str(header_type.parse_next(input)?).unwrap();
 
-    let attributes = kvs(input);
+    let attributes = kvs(input)?
+        .into_iter()
+        .map(|(k, v)| {
+            Attribute::try_from((k, v))
+                .map_err(|error| invalid(input, format!("`{k}` attribute: {error}")))
+        })
+        .collect::<Result<_, _>>()?;
     Ok(HeaderTag {
         tag_type,
-        attributes: attributes?.into_attributes(),
+        attributes,
     })
 }
 
 fn header_type<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
-    alt((literal("xml-stylesheet"), literal("xml"))).parse_next(input)
+    literal("xml-stylesheet").parse_next(input)
 }
 
 #[cfg(test)]
@@ -68,15 +283,12 @@ mod tests {
 
     use super::*;
 
-    use crate::attributes::{Encoding, Version};
-
-    #[test]
-    fn test_xml_tag() {
-        let input = r#"xml"#;
-
-        let output = HeaderTagType::from_str(input).unwrap();
-
-        assert_eq!(output, HeaderTagType::Xml);
+    fn utf8() -> Option<XmlDeclaration> {
+        Some(XmlDeclaration {
+            version: Version::One,
+            encoding: Some(Encoding::Utf8),
+            standalone: None,
+        })
     }
 
     #[test]
@@ -91,7 +303,7 @@ mod tests {
 
     #[test]
     fn test_parse_header() {
-        let mut input = r#"<?xml?>"#;
+        let mut input = r#"<?xml version="1.0"?>"#;
 
         let output = Header::parse(&mut input).unwrap();
 
@@ -99,10 +311,12 @@ mod tests {
         assert_eq!(
             output,
             Header {
-                tags: vec![HeaderTag {
-                    tag_type: HeaderTagType::Xml,
-                    attributes: vec![],
-                }]
+                declaration: Some(XmlDeclaration {
+                    version: Version::One,
+                    encoding: None,
+                    standalone: None,
+                }),
+                ..Header::default()
             }
         );
     }
@@ -117,13 +331,8 @@ mod tests {
         assert_eq!(
             output,
             Header {
-                tags: vec![HeaderTag {
-                    tag_type: HeaderTagType::Xml,
-                    attributes: vec![
-                        Attribute::Version(Version::One),
-                        Attribute::Encoding(Encoding::Utf8)
-                    ],
-                }]
+                declaration: utf8(),
+                ..Header::default()
             }
         );
     }
@@ -141,11 +350,27 @@ mod tests {
                 tags: vec![HeaderTag {
                     tag_type: HeaderTagType::XmlStyleSheet,
                     attributes: vec![Attribute::Type(TEXT_CSS), Attribute::Href("uslm.css"),]
-                }]
+                }],
+                ..Header::default()
             }
         );
     }
 
+    #[test]
+    fn test_parse_header_invalid_attribute() {
+        let mut input = r#"<?xml-stylesheet type="css" href="uslm.css"?>"#;
+
+        let error = Header::parse(&mut input).unwrap_err();
+
+        let Ok(error) = error.into_inner() else {
+            panic!("not a cut");
+        };
+        assert_eq!(
+            error.to_string(),
+            "`type` attribute: mime parse error: a slash (/) was missing between the type and subtype"
+        );
+    }
+
     #[test]
     fn test_parse_multi_header() {
         let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?>"#;
@@ -155,23 +380,116 @@ mod tests {
         assert_eq!(
             output,
             Header {
-                tags: vec![
-                    HeaderTag {
-                        tag_type: HeaderTagType::Xml,
-                        attributes: vec![
-                            Attribute::Version(Version::One),
-                            Attribute::Encoding(Encoding::Utf8)
-                        ],
-                    },
-                    HeaderTag {
-                        tag_type: HeaderTagType::XmlStyleSheet,
-                        attributes: vec![Attribute::Type(TEXT_CSS), Attribute::Href("uslm.css"),]
-                    }
-                ]
+                declaration: utf8(),
+                tags: vec![HeaderTag {
+                    tag_type: HeaderTagType::XmlStyleSheet,
+                    attributes: vec![Attribute::Type(TEXT_CSS), Attribute::Href("uslm.css"),]
+                }],
+                doctype: None,
             }
         );
     }
 
+    #[test]
+    fn test_parse_header_xml_1_1_standalone() {
+        let mut input =
+            "\u{feff}\n<?xml version='1.1' encoding = \"utf-8\"\tstandalone=\"yes\" ?>\n<uscDoc/>";
+
+        let output = Header::parse(&mut input).unwrap();
+
+        assert_eq!(input, "<uscDoc/>");
+        assert_eq!(
+            output.declaration,
+            Some(XmlDeclaration {
+                version: Version::OnePointOne,
+                encoding: Some(Encoding::Utf8),
+                standalone: Some(true),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_header_invalid_declaration() {
+        for (mut input, message) in [
+            (
+                r#"<?xml encoding="UTF-8"?>"#,
+                "the XML declaration must start with `version`",
+            ),
+            (r#"<?xml version="2.0"?>"#, "unsupported XML version `2.0`"),
+            (
+                r#"<?xml version="1.0" standalone="yes" encoding="UTF-8"?>"#,
+                "unexpected `encoding` in the XML declaration",
+            ),
+            (
+                r#"<?xml version="1.0" standalone="maybe"?>"#,
+                "invalid `standalone` value `maybe`",
+            ),
+        ] {
+            let error = Header::parse(&mut input).unwrap_err();
+            let Ok(error) = error.into_inner() else {
+                panic!("{input}");
+            };
+            assert_eq!(error.to_string(), message, "{input}");
+        }
+        let mut input = r#"<?xml version="1.0" encoding="UTF 8"?>"#;
+        assert!(Header::parse(&mut input).is_err());
+        assert_eq!(
+            XmlDeclaration::from_pairs(&[("version", "1.0"), ("lang", "en")])
+                .unwrap_err()
+                .to_string(),
+            "unexpected `lang` in the XML declaration"
+        );
+    }
+
+    #[test]
+    fn test_parse_header_doctype() {
+        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Generated from the House XML -->
+<!DOCTYPE bill PUBLIC "-//US House//DTD bill//EN" "bill.dtd" [
+  <!ENTITY sect "&#167;">
+  <!-- a ] in a comment -->
+  <!ATTLIST bill note CDATA "a ] in a literal">
+]>
+<?xml-stylesheet type="text/css" href="uslm.css"?>
+<bill/>"#;
+
+        let output = Header::parse(&mut input).unwrap();
+
+        assert_eq!(input, "<bill/>");
+        assert_eq!(output.declaration, utf8());
+        assert_eq!(output.tags.len(), 1);
+        assert_eq!(
+            output.doctype,
+            Some(Doctype {
+                name: "bill",
+                external_id: Some(ExternalId::Public {
+                    public_id: "-//US House//DTD bill//EN",
+                    system_id: "bill.dtd",
+                }),
+                internal_subset: Some(
+                    "\n  <!ENTITY sect \"&#167;\">\n  <!-- a ] in a comment -->\n  <!ATTLIST bill note CDATA \"a ] in a literal\">\n"
+                ),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_header_doctype_system() {
+        let mut input = r#"<!DOCTYPE uscDoc SYSTEM "uslm.dtd"><uscDoc/>"#;
+
+        let output = Header::parse(&mut input).unwrap();
+
+        assert_eq!(input, "<uscDoc/>");
+        assert_eq!(
+            output.doctype,
+            Some(Doctype {
+                name: "uscDoc",
+                external_id: Some(ExternalId::System("uslm.dtd")),
+                internal_subset: None,
+            })
+        );
+    }
+
     #[test]
     fn test_delimited() {
         let mut input = r#"<?abc?><?abc?> TEST"#;
@@ -0,0 +1,285 @@
+use std::fmt::Write;
+
+use crate::{
+    attributes::Attribute,
+    common::escape,
+    header::HeaderTagType,
+    tags::{Core, Generic, Other, Primitive, Tag, TagType},
+    Uslm,
+};
+
+/// Turns the `href` of a `ref` element into the target of an HTML link.
+pub trait HrefResolver {
+    fn resolve(&self, href: &str) -> String;
+}
+
+/// Links every `ref` to its `href` unchanged.
+#[derive(Debug, Default)]
+pub struct IdentityResolver;
+
+impl HrefResolver for IdentityResolver {
+    fn resolve(&self, href: &str) -> String {
+        href.to_string()
+    }
+}
+
+impl<F: Fn(&str) -> String> HrefResolver for F {
+    fn resolve(&self, href: &str) -> String {
+        self(href)
+    }
+}
+
+/// Renders a USLM document as HTML5, using the USLM element name of
+/// every tag as its class so `uslm.css` style rules carry over.
+#[derive(Debug, Default)]
+pub struct HtmlRenderer<R = IdentityResolver> {
+    resolver: R,
+}
+
+impl HtmlRenderer {
+    pub fn new() -> Self {
+        HtmlRenderer {
+            resolver: IdentityResolver,
+        }
+    }
+}
+
+impl<R: HrefResolver> HtmlRenderer<R> {
+    pub fn with_resolver(resolver: R) -> Self {
+        HtmlRenderer { resolver }
+    }
+
+    /// Renders a complete HTML document, linking the stylesheet
+    /// referenced by the `xml-stylesheet` header when there is one.
+    pub fn render(&self, uslm: &Uslm) -> String {
+        let mut out = String::from("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+        let stylesheet = uslm
+            .header
+            .tags
+            .iter()
+            .filter(|tag| tag.tag_type == HeaderTagType::XmlStyleSheet)
+            .flat_map(|tag| tag.attributes.iter())
+            .find_map(|attribute| match attribute {
+                Attribute::Href(href) => Some(*href),
+                _ => None,
+            });
+        if let Some(href) = stylesheet {
+            let _ = write!(out, "<link rel=\"stylesheet\" href=\"{}\">", escape(href));
+        }
+        out.push_str("</head><body>");
+        for tag in &uslm.content {
+            self.write_tag(&mut out, tag, 0);
+        }
+        out.push_str("</body></html>\n");
+        out
+    }
+
+    /// Renders a single tag and its descendants as an HTML fragment.
+    pub fn render_tag(&self, tag: &Tag) -> String {
+        let mut out = String::new();
+        self.write_tag(&mut out, tag, 0);
+        out
+    }
+
+    /// `depth` counts the levels enclosing `tag` and picks the rank of
+    /// the `h1`-`h6` element used for headings, `h1` for the heading of
+    /// an outermost level.
+    fn write_tag(&self, out: &mut String, tag: &Tag, depth: usize) {
+        let element = match element(&tag.tag_type) {
+            Some(element) => element,
+            None => return,
+        };
+        let element = match element {
+            "h" => ["h1", "h2", "h3", "h4", "h5", "h6"][depth.saturating_sub(1).min(5)],
+            _ => element,
+        };
+
+        let _ = write!(out, "<{element}");
+        let anchor = tag.id().or_else(|| tag.identifier());
+        if let Some(anchor) = anchor {
+            let _ = write!(out, " id=\"{}\"", escape(anchor));
+        }
+
+        if let TagType::Table(_) = tag.tag_type {
+            // Embedded XHTML tables keep their own presentational attributes.
+            for attribute in &tag.attributes {
+                let (key, value) = match attribute {
+                    Attribute::Class(value) => ("class", value.raw()),
+                    Attribute::Style(value) => ("style", *value),
+                    Attribute::ColSpan(value) => ("colspan", value.raw()),
+                    Attribute::RowSpan(value) => ("rowspan", value.raw()),
+                    _ => continue,
+                };
+                let _ = write!(out, " {key}=\"{}\"", escape(value));
+            }
+        } else {
+            let _ = write!(out, " class=\"{}", tag.tag_type.name());
+            for attribute in &tag.attributes {
+                if let Attribute::Class(class) = attribute {
+                    let _ = write!(out, " {}", escape(class.raw()));
+                }
+            }
+            out.push('"');
+        }
+
+        for attribute in &tag.attributes {
+            match (&tag.tag_type, attribute) {
+                (TagType::Core(Core::Ref), Attribute::Href(href)) => {
+                    let href = self.resolver.resolve(href);
+                    let _ = write!(out, " href=\"{}\"", escape(&href));
+                }
+                (TagType::Core(Core::Date), Attribute::Date(date)) => {
+                    let _ = write!(out, " datetime=\"{}\"", escape(date.raw()));
+                }
+                _ => {}
+            }
+        }
+        out.push('>');
+
+        if element == "col" {
+            return;
+        }
+
+        let depth = match tag.tag_type {
+            TagType::Level(_) | TagType::Core(Core::Level) => depth + 1,
+            _ => depth,
+        };
+        for child in &tag.children {
+            self.write_tag(out, child, depth);
+        }
+        if let Some(content) = &tag.content {
+            // Content is still XML-escaped, which is valid HTML as-is.
+            out.push_str(content);
+        }
+        let _ = write!(out, "</{element}>");
+    }
+}
+
+/// The HTML element used for a tag type, `"h"` standing in for the
+/// heading rank chosen by nesting depth. `None` drops the tag entirely.
+fn element(tag_type: &TagType) -> Option<&'static str> {
+    let element = match tag_type {
+        TagType::Core(Core::Meta) | TagType::Meta(_) | TagType::Property(_) => return None,
+        TagType::Core(Core::LawDoc | Core::Document) | TagType::Doc(_) => "article",
+        TagType::Core(Core::Level) | TagType::Level(_) => "section",
+        TagType::Core(Core::Heading) => "h",
+        TagType::Core(Core::Num | Core::Date) => "span",
+        TagType::Core(Core::Ref) => "a",
+        TagType::Core(Core::Toc) => "nav",
+        TagType::Core(Core::QuotedContent) => "blockquote",
+        TagType::Core(Core::QuotedText) => "q",
+        TagType::Core(Core::Notes | Core::Note) | TagType::Note(_) => "aside",
+        TagType::Core(Core::Text) | TagType::Other(Other::Chapeau | Other::Continuation) => "p",
+        TagType::Other(Other::Term) => "dfn",
+        TagType::Other(Other::Def | Other::Proviso) => "span",
+        TagType::Generic(Generic::Header) => "header",
+        TagType::Generic(Generic::B) => "b",
+        TagType::Generic(Generic::I) => "i",
+        TagType::Primitive(Primitive::Marker | Primitive::Inline) => "span",
+        TagType::Table(table) => table.name(),
+        _ => "div",
+    };
+    Some(element)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render_level() {
+        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?>
+<lawDoc identifier="/us/usc/t5">
+   <meta>
+      <docNumber>5</docNumber>
+   </meta>
+   <main>
+      <chapter identifier="/us/usc/t5/ch1">
+         <num value="1">CHAPTER 1.</num>
+         <heading>General Provisions</heading>
+         <section id="s101">
+            <num value="101">Sec. 101.</num>
+            <heading>Executive departments</heading>
+            <content>The Executive departments are</content>
+            <continuation><ref href="/us/usc/t5/s102">section 102</ref></continuation>
+         </section>
+      </chapter>
+   </main>
+</lawDoc>"#;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = HtmlRenderer::new().render(&uslm);
+
+        assert_eq!(
+            output,
+            concat!(
+                "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">",
+                "<link rel=\"stylesheet\" href=\"uslm.css\"></head><body>",
+                "<article id=\"/us/usc/t5\" class=\"lawDoc\">",
+                "<div class=\"main\">",
+                "<section id=\"/us/usc/t5/ch1\" class=\"chapter\">",
+                "<span class=\"num\">CHAPTER 1.</span>",
+                "<h1 class=\"heading\">General Provisions</h1>",
+                "<section id=\"s101\" class=\"section\">",
+                "<span class=\"num\">Sec. 101.</span>",
+                "<h2 class=\"heading\">Executive departments</h2>",
+                "<div class=\"content\">The Executive departments are</div>",
+                "<p class=\"continuation\"><a class=\"ref\" href=\"/us/usc/t5/s102\">section 102</a></p>",
+                "</section></section></div></article>",
+                "</body></html>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_ref_with_resolver() {
+        let mut input = r#"<ref href="/us/usc/t5/s552">section 552</ref>"#;
+
+        let tags = crate::tags::parse(&mut input).unwrap();
+        let renderer =
+            HtmlRenderer::with_resolver(|href: &str| format!("https://uscode.house.gov{href}"));
+
+        let output = renderer.render_tag(&tags[0]);
+
+        assert_eq!(
+            output,
+            r#"<a class="ref" href="https://uscode.house.gov/us/usc/t5/s552">section 552</a>"#
+        );
+    }
+
+    #[test]
+    fn test_render_table_passthrough() {
+        let mut input = r#"<table class="centered"><tr><th colspan="2">Agency</th></tr><tr><td>Labor</td><td>Treasury</td></tr></table>"#;
+
+        let tags = crate::tags::parse(&mut input).unwrap();
+
+        let output = HtmlRenderer::new().render_tag(&tags[0]);
+
+        assert_eq!(
+            output,
+            r#"<table class="centered"><tr><th colspan="2">Agency</th></tr><tr><td>Labor</td><td>Treasury</td></tr></table>"#
+        );
+    }
+
+    #[test]
+    fn test_render_escapes_ampersands() {
+        let mut input = r#"<ref href="/us/usc/t5/s552">section 552</ref>"#;
+
+        let tags = crate::tags::parse(&mut input).unwrap();
+        let renderer = HtmlRenderer::with_resolver(|href: &str| format!("/search?q={href}&page=1"));
+
+        let output = renderer.render_tag(&tags[0]);
+
+        assert_eq!(
+            output,
+            r#"<a class="ref" href="/search?q=/us/usc/t5/s552&amp;page=1">section 552</a>"#
+        );
+        assert_eq!(
+            escape("R&D <\"x\"> &#167; &#xA7; & ;"),
+            "R&amp;D &lt;&quot;x&quot;&gt; &#167; &#xA7; &amp; ;"
+        );
+    }
+}
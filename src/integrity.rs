@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use crate::{
+    attributes::Attribute,
+    edit::prefix,
+    span::Span,
+    tags::{Core, Level, Tag, TagType},
+    validate::Diagnostic,
+    Uslm,
+};
+
+impl<'s> Uslm<'s> {
+    /// Checks that the pointers of the document resolve: `id`s are unique,
+    /// every `idref` names an `id`, `href`s into the document name an
+    /// existing `identifier` or, as `#id`, an existing `id`, and the
+    /// identifier of each level extends the identifier of the level it
+    /// sits in.
+    pub fn check_integrity(&self) -> Vec<Diagnostic> {
+        let mut targets = Targets::default();
+        let mut diagnostics = Vec::new();
+        for tag in &self.content {
+            targets.collect(tag, false, &mut diagnostics);
+        }
+        for tag in &self.content {
+            let document = tag.identifier();
+            check(tag, document, document, &targets, false, &mut diagnostics);
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+        diagnostics
+    }
+}
+
+/// The `id`s and identifiers of the document. Those inside
+/// `quotedContent` belong to the quoted document: its `id`s are kept
+/// apart and only resolve `idref`s quoted along with them.
+#[derive(Default)]
+struct Targets<'s> {
+    ids: HashSet<&'s str>,
+    identifiers: HashSet<&'s str>,
+    quoted_ids: HashSet<&'s str>,
+}
+
+impl<'s> Targets<'s> {
+    /// Records the `id`s and identifiers of `tag` and its descendants,
+    /// reporting `id`s of the document seen before.
+    fn collect(&mut self, tag: &Tag<'s>, quoted: bool, diagnostics: &mut Vec<Diagnostic>) {
+        for (index, attribute) in tag.attributes.iter().enumerate() {
+            match attribute {
+                Attribute::Id(id) if quoted => {
+                    self.quoted_ids.insert(id);
+                }
+                Attribute::Id(id) if !self.ids.insert(id) => {
+                    diagnostics.push(Diagnostic::error(
+                        "duplicate-id",
+                        format!("the `id` `{id}` is used more than once"),
+                        attribute_span(tag, index),
+                    ));
+                }
+                Attribute::Identifier(identifier) if !quoted => {
+                    self.identifiers.insert(identifier);
+                }
+                _ => {}
+            }
+        }
+        let quoted = quoted || tag.tag_type == TagType::Core(Core::QuotedContent);
+        for child in &tag.children {
+            self.collect(child, quoted, diagnostics);
+        }
+    }
+}
+
+/// `document` is the identifier of the document and `parent` the one
+/// levels inside `tag` must extend. Inside `quotedContent`, which quotes
+/// another document, only `idref`s are checked, and they may also name
+/// the quoted `id`s.
+fn check(
+    tag: &Tag,
+    document: Option<&str>,
+    parent: Option<&str>,
+    targets: &Targets,
+    quoted: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (index, attribute) in tag.attributes.iter().enumerate() {
+        match attribute {
+            Attribute::Idref(idref)
+                if !targets.ids.contains(idref)
+                    && (!quoted || !targets.quoted_ids.contains(idref)) =>
+            {
+                diagnostics.push(Diagnostic::error(
+                    "dangling-idref",
+                    format!("the `idref` `{idref}` names no `id` of the document"),
+                    attribute_span(tag, index),
+                ));
+            }
+            Attribute::Href(href) if !quoted => {
+                let missing = match href.strip_prefix('#') {
+                    Some(id) => !targets.ids.contains(id),
+                    None => within(href, document) && !targets.identifiers.contains(href),
+                };
+                if missing {
+                    diagnostics.push(Diagnostic::error(
+                        "dangling-href",
+                        format!("the `href` `{href}` points to nothing in the document"),
+                        attribute_span(tag, index),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let quoted = quoted || tag.tag_type == TagType::Core(Core::QuotedContent);
+    let mut parent = parent;
+    if let (TagType::Level(level), Some(identifier), false) =
+        (&tag.tag_type, tag.identifier(), quoted)
+    {
+        if let Some(message) = misplaced(tag, *level, identifier, parent) {
+            diagnostics.push(Diagnostic::warning(
+                "identifier-hierarchy",
+                message,
+                tag.span(),
+            ));
+        }
+        // Levels above the section are left out of the identifiers of
+        // the levels they hold, as in `/us/usc/t5/s552` in chapter 5.
+        if level.rank() >= Level::Section.rank() {
+            parent = Some(identifier);
+        }
+    }
+    for child in &tag.children {
+        check(child, document, parent, targets, quoted, diagnostics);
+    }
+}
+
+/// Why the identifier of a level does not fit its position: it must
+/// extend `parent` and end with the `value` of the level's `num`.
+fn misplaced(tag: &Tag, level: Level, identifier: &str, parent: Option<&str>) -> Option<String> {
+    if let Some(parent) = parent.filter(|parent| !within(identifier, Some(parent))) {
+        return Some(format!(
+            "the identifier `{identifier}` of a `{}` is not under `{parent}`",
+            level.name()
+        ));
+    }
+    let value = tag
+        .children
+        .iter()
+        .filter(|child| child.tag_type == TagType::Core(Core::Num))
+        .flat_map(|num| &num.attributes)
+        .find_map(|attribute| match attribute {
+            Attribute::Value(value) => Some(*value),
+            _ => None,
+        })?;
+    let last = identifier.rsplit('/').next().unwrap_or(identifier);
+    let designation = last.strip_prefix(prefix(level)).unwrap_or(last);
+    (designation != value)
+        .then(|| format!("the identifier `{identifier}` does not match the designation `{value}`"))
+}
+
+/// Whether `identifier` is strictly under `parent`.
+fn within(identifier: &str, parent: Option<&str>) -> bool {
+    parent
+        .and_then(|parent| identifier.strip_prefix(parent))
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn attribute_span(tag: &Tag, index: usize) -> Span {
+    tag.spans
+        .attributes
+        .get(index)
+        .copied()
+        .unwrap_or_else(|| tag.span())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn messages(input: &str) -> Vec<(&'static str, String)> {
+        let mut input = input;
+        let uslm = Uslm::parse(&mut input).unwrap();
+        uslm.check_integrity()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_integrity_consistent_document() {
+        let input = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<chapter identifier="/us/usc/t5/ch5"><num value="5">CHAPTER 5.</num>
+<section id="s552" identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num>
+<subsection identifier="/us/usc/t5/s552/a"><num value="a">(a)</num><content><ref idref="s552">this section</ref><ref href="/us/usc/t5/s552/a">subsection (a)</ref><ref href="/us/usc/t42/s1983">section 1983 of title 42</ref></content></subsection>
+</section>
+</chapter>
+</main>
+</uscDoc>"#;
+
+        assert_eq!(messages(input), vec![]);
+    }
+
+    #[test]
+    fn test_check_integrity() {
+        let input = r##"<uscDoc identifier="/us/usc/t5">
+<main>
+<section id="s552" identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num>
+<subsection id="s552" identifier="/us/usc/t5/s553/a"><num value="a">(a)</num><content><ref idref="s551">section 551</ref></content></subsection>
+<subsection identifier="/us/usc/t5/s552/c"><num value="b">(b)</num><content><ref href="/us/usc/t5/s552/d">subsection (d)</ref><ref href="#s1">section 1</ref></content></subsection>
+<quotedContent><section identifier="/us/usc/t42/s1"><content><ref href="/us/usc/t5/s9">section 9</ref></content></section></quotedContent>
+</section>
+</main>
+</uscDoc>"##;
+
+        assert_eq!(
+            messages(input),
+            vec![
+                (
+                    "identifier-hierarchy",
+                    "the identifier `/us/usc/t5/s553/a` of a `subsection` is not under `/us/usc/t5/s552`".to_string()
+                ),
+                (
+                    "duplicate-id",
+                    "the `id` `s552` is used more than once".to_string()
+                ),
+                (
+                    "dangling-idref",
+                    "the `idref` `s551` names no `id` of the document".to_string()
+                ),
+                (
+                    "identifier-hierarchy",
+                    "the identifier `/us/usc/t5/s552/c` does not match the designation `b`".to_string()
+                ),
+                (
+                    "dangling-href",
+                    "the `href` `/us/usc/t5/s552/d` points to nothing in the document".to_string()
+                ),
+                (
+                    "dangling-href",
+                    "the `href` `#s1` points to nothing in the document".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_integrity_designation_and_quoted_targets() {
+        let input = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<section id="s552" identifier="/us/usc/t5/s552"><num value="52">Sec. 52.</num>
+<subsection identifier="/us/usc/t5/s552/a"><num value="a">(a)</num><content><ref href="/us/usc/t5/s552/b">subsection (b)</ref></content></subsection>
+<quotedContent><section id="s552" identifier="/us/usc/t5/s552/b"><num value="b">(b)</num><content><ref idref="s552">this section</ref></content></section></quotedContent>
+</section>
+</main>
+</uscDoc>"#;
+
+        assert_eq!(
+            messages(input),
+            vec![
+                (
+                    "identifier-hierarchy",
+                    "the identifier `/us/usc/t5/s552` does not match the designation `52`"
+                        .to_string()
+                ),
+                (
+                    "dangling-href",
+                    "the `href` `/us/usc/t5/s552/b` points to nothing in the document".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,7 +1,53 @@
+mod amend;
 mod attributes;
+mod builder;
+mod chunks;
+mod citation;
 mod common;
+mod definitions;
+mod designation;
+mod diff;
+mod edit;
+mod enactment;
 mod header;
+mod html;
+mod integrity;
+mod lint;
+mod markdown;
+mod notes;
+mod redline;
+mod reference;
+mod span;
 mod tags;
+mod temporal;
+mod toc;
 mod uslm;
+mod validate;
 
+pub use self::amend::{Amended, Amendment, AmendmentAction, Anchor};
+pub use self::attributes::{
+    kind, Attribute, AttributeKind, Attributes, Encoding, Orientation, Typed, Version,
+};
+pub use self::builder::{BillBuilder, LevelBuilder};
+pub use self::chunks::{Chunk, Chunks};
+pub use self::citation::{citations, Citation};
+pub use self::definitions::{Definition, Extent, Scope};
+pub use self::designation::{Designation, DesignationKind};
+pub use self::diff::{diff, Change, WordDiff};
+pub use self::edit::Arena;
+pub use self::enactment::{enactments, Enactment, PublicLaw, SourceCredit, StatutesAtLarge};
+pub use self::header::{Doctype, ExternalId, Header, HeaderTag, HeaderTagType, XmlDeclaration};
+pub use self::html::{HrefResolver, HtmlRenderer, IdentityResolver};
+pub use self::lint::{ConfigValue, Lint, LintConfig, Linter};
+pub use self::markdown::MarkdownRenderer;
+pub use self::notes::{NoteEntry, NoteKind, Notes, Topic};
+pub use self::redline::RedlineRenderer;
+pub use self::reference::{Reference, ReferenceGraph, UslmRef};
+pub use self::span::{LineIndex, Position, Span, Spans};
+pub use self::tags::{
+    Appendix, Core, Dc, Doc, Generic, Level, Meta, Note, Other, Primitive, Property, Signature,
+    Table, Tag, TagType, Title,
+};
+pub use self::temporal::{Date, TemporalVersion};
 pub use self::uslm::Uslm;
+pub use self::validate::{Diagnostic, Severity};
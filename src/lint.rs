@@ -0,0 +1,733 @@
+use std::str::FromStr;
+
+use crate::{
+    tags::{Core, Level, Other, Primitive, Tag, TagType, HIERARCHY},
+    validate::{Diagnostic, Severity},
+    Uslm,
+};
+
+/// A house-style rule, checked against every tag of a document in turn.
+pub trait Lint {
+    /// Short machine-readable name of the rule, used as the code of its
+    /// diagnostics and to configure it.
+    fn code(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Sets an option of the rule from its table in a [`LintConfig`].
+    /// `enabled` and `severity` are handled by the [`Linter`].
+    fn configure(&mut self, key: &str, value: &ConfigValue) -> Result<(), String> {
+        let _ = value;
+        Err(format!("unknown option `{key}`"))
+    }
+
+    /// Reports the problems with `tag`, `parent` being the tag it sits in.
+    /// The severity of the diagnostics is replaced by the configured one.
+    fn check(&self, tag: &Tag, parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>);
+}
+
+/// Runs a set of [`Lint`]s over documents. [`Linter::new`] starts with the
+/// built-in rules:
+///
+/// - `heading-capitalization`: headings in upper case above the section
+///   and in sentence case from the section down, configurable per level
+///   with `upper`, `title`, `sentence` or `any`
+/// - `missing-num`: a level without a `num`
+/// - `empty-content`: a `content` without any text
+/// - `num-trailing-whitespace`: a `num` ending with whitespace
+/// - `chapeau-without-sublevels`: a `chapeau` no level follows
+/// - `continuation-without-sublevels`: a `continuation` no level precedes
+pub struct Linter {
+    rules: Vec<Rule>,
+}
+
+struct Rule {
+    lint: Box<dyn Lint>,
+    enabled: bool,
+    severity: Severity,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter::new()
+    }
+}
+
+impl Linter {
+    /// A linter with the built-in rules, all enabled at their default
+    /// severities.
+    pub fn new() -> Self {
+        let mut linter = Linter { rules: Vec::new() };
+        linter.register(HeadingCapitalization::default());
+        linter.register(MissingNum);
+        linter.register(EmptyContent);
+        linter.register(NumTrailingWhitespace);
+        linter.register(ChapeauWithoutSublevels);
+        linter.register(ContinuationWithoutSublevels);
+        linter
+    }
+
+    pub fn register(&mut self, lint: impl Lint + 'static) {
+        self.rules.push(Rule {
+            severity: lint.default_severity(),
+            enabled: true,
+            lint: Box::new(lint),
+        });
+    }
+
+    /// The code of each rule and its severity, `None` when disabled.
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, Option<Severity>)> + '_ {
+        self.rules
+            .iter()
+            .map(|rule| (rule.lint.code(), rule.enabled.then_some(rule.severity)))
+    }
+
+    /// Applies the settings of `config`, failing on rules and options the
+    /// linter does not know.
+    pub fn configure(&mut self, config: &LintConfig) -> Result<(), String> {
+        for (name, options) in &config.rules {
+            let rule = self
+                .rules
+                .iter_mut()
+                .find(|rule| rule.lint.code() == name)
+                .ok_or_else(|| format!("unknown rule `{name}`"))?;
+            for (key, value) in options {
+                match (key.as_str(), value) {
+                    ("enabled", ConfigValue::Boolean(enabled)) => rule.enabled = *enabled,
+                    ("severity", ConfigValue::String(severity)) => {
+                        rule.severity = match severity.as_str() {
+                            "warning" => Severity::Warning,
+                            "error" => Severity::Error,
+                            _ => return Err(format!("`{name}`: unknown severity `{severity}`")),
+                        }
+                    }
+                    ("enabled" | "severity", _) => {
+                        return Err(format!("`{name}`: `{key}` has the wrong type"))
+                    }
+                    _ => rule
+                        .lint
+                        .configure(key, value)
+                        .map_err(|message| format!("`{name}`: {message}"))?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The diagnostics of the enabled rules, in document order.
+    pub fn lint(&self, uslm: &Uslm) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for tag in &uslm.content {
+            self.lint_tag(tag, None, &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    fn lint_tag(&self, tag: &Tag, parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        for rule in self.rules.iter().filter(|rule| rule.enabled) {
+            let start = diagnostics.len();
+            rule.lint.check(tag, parent, diagnostics);
+            for diagnostic in &mut diagnostics[start..] {
+                diagnostic.severity = rule.severity;
+            }
+        }
+        for child in &tag.children {
+            self.lint_tag(child, Some(tag), diagnostics);
+        }
+    }
+}
+
+/// Settings for the rules of a [`Linter`], read from a TOML file with a
+/// table per rule:
+///
+/// ```toml
+/// [rules.heading-capitalization]
+/// severity = "error"
+/// section = "title"
+///
+/// [rules.empty-content]
+/// enabled = false
+/// ```
+///
+/// Only a subset of TOML is accepted: `[rules.<name>]` table headers,
+/// `key = value` lines whose value is a single-line string, a boolean or
+/// an integer, and comments. Dotted keys, inline tables, arrays, arrays
+/// of tables, multi-line strings, floats and dates are rejected with an
+/// error naming the unsupported form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    rules: Vec<(String, Vec<(String, ConfigValue)>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValue {
+    String(String),
+    Boolean(bool),
+    Integer(i64),
+}
+
+impl FromStr for LintConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = LintConfig::default();
+        for (number, line) in s.lines().enumerate() {
+            let error = |message: String| format!("line {}: {message}", number + 1);
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with("[[") {
+                return Err(error("arrays of tables are not supported".to_string()));
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                let table = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("unclosed table header".to_string()))?
+                    .trim();
+                let rule = table
+                    .strip_prefix("rules.")
+                    .ok_or_else(|| error(format!("unknown table `{table}`")))?;
+                config
+                    .rules
+                    .push((unquote(rule.trim()).to_string(), Vec::new()));
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `key = value`".to_string()))?;
+            let key = key.trim();
+            if key.contains('.') && unquote(key) == key {
+                return Err(error(format!(
+                    "dotted key `{key}` is not supported, use a `[rules.<name>]` table"
+                )));
+            }
+            let value = parse_value(value.trim()).map_err(error)?;
+            let (_, options) = config
+                .rules
+                .last_mut()
+                .ok_or_else(|| error("option outside a `[rules.<name>]` table".to_string()))?;
+            options.push((unquote(key).to_string(), value));
+        }
+        Ok(config)
+    }
+}
+
+/// The line up to a `#` outside of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(open), c) if c == open && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..index],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn unquote(key: &str) -> &str {
+    key.strip_prefix('"')
+        .and_then(|key| key.strip_suffix('"'))
+        .unwrap_or(key)
+}
+
+fn parse_value(value: &str) -> Result<ConfigValue, String> {
+    match value {
+        "true" => return Ok(ConfigValue::Boolean(true)),
+        "false" => return Ok(ConfigValue::Boolean(false)),
+        _ => {}
+    }
+    if value.starts_with("\"\"\"") || value.starts_with("'''") {
+        return Err("multi-line strings are not supported".to_string());
+    }
+    if value.starts_with('{') {
+        return Err("inline tables are not supported".to_string());
+    }
+    if value.starts_with('[') {
+        return Err("arrays are not supported".to_string());
+    }
+    if let Some(literal) = value.strip_prefix('\'') {
+        return match literal.strip_suffix('\'') {
+            Some(literal) if !literal.contains('\'') => Ok(ConfigValue::String(literal.into())),
+            _ => Err(format!("malformed string `{value}`")),
+        };
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut string = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if chars.as_str().is_empty() => return Ok(ConfigValue::String(string)),
+                '"' => break,
+                '\\' => string.push(match chars.next() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    _ => return Err(format!("unsupported escape in `{value}`")),
+                }),
+                c => string.push(c),
+            }
+        }
+        return Err(format!("malformed string `{value}`"));
+    }
+    value
+        .replace('_', "")
+        .parse()
+        .map(ConfigValue::Integer)
+        .map_err(|_| format!("unsupported value `{value}`"))
+}
+
+fn is_level(tag: &Tag) -> bool {
+    matches!(tag.tag_type, TagType::Level(_) | TagType::Core(Core::Level))
+}
+
+/// The position of `tag` among the children of `parent`.
+fn index_in(tag: &Tag, parent: &Tag) -> Option<usize> {
+    parent
+        .children
+        .iter()
+        .position(|child| std::ptr::eq(child, tag))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Capitalization {
+    /// Every letter in upper case, as in `GENERAL PROVISIONS`.
+    Upper,
+    /// Every word capitalized but articles, conjunctions and short
+    /// prepositions, as in `Freedom of Information`.
+    Title,
+    /// Only the first word capitalized, as in `Short title`.
+    Sentence,
+    Any,
+}
+
+/// Words left in lower case inside a title-case heading.
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "the", "to", "with",
+];
+
+impl Capitalization {
+    fn name(&self) -> &'static str {
+        match self {
+            Capitalization::Upper => "upper case",
+            Capitalization::Title => "title case",
+            Capitalization::Sentence => "sentence case",
+            Capitalization::Any => "any case",
+        }
+    }
+
+    fn matches(&self, heading: &str) -> bool {
+        let starts_lowercase = |word: &str| word.chars().next().is_some_and(char::is_lowercase);
+        let mut words = heading
+            .split_whitespace()
+            .map(|word| word.trim_start_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty());
+        match self {
+            Capitalization::Upper => !heading.chars().any(char::is_lowercase),
+            Capitalization::Title => words.enumerate().all(|(index, word)| {
+                !starts_lowercase(word) || index > 0 && MINOR_WORDS.contains(&word)
+            }),
+            // Single words in capitals are taken for acronyms.
+            Capitalization::Sentence => {
+                words.next().is_none_or(|word| !starts_lowercase(word))
+                    && (words.next().is_none() || heading.chars().any(char::is_lowercase))
+            }
+            Capitalization::Any => true,
+        }
+    }
+}
+
+struct HeadingCapitalization {
+    styles: Vec<(Level, Capitalization)>,
+}
+
+impl Default for HeadingCapitalization {
+    fn default() -> Self {
+        let styles = HIERARCHY
+            .iter()
+            .map(|level| match level {
+                level if *level < Level::Section => (*level, Capitalization::Upper),
+                level => (*level, Capitalization::Sentence),
+            })
+            .collect();
+        HeadingCapitalization { styles }
+    }
+}
+
+impl Lint for HeadingCapitalization {
+    fn code(&self) -> &'static str {
+        "heading-capitalization"
+    }
+
+    /// Options are level names, as in `section = "title"`.
+    fn configure(&mut self, key: &str, value: &ConfigValue) -> Result<(), String> {
+        let (_, style) = self
+            .styles
+            .iter_mut()
+            .find(|(level, _)| level.name() == key)
+            .ok_or_else(|| format!("unknown level `{key}`"))?;
+        *style = match value {
+            ConfigValue::String(value) => match value.as_str() {
+                "upper" => Capitalization::Upper,
+                "title" => Capitalization::Title,
+                "sentence" => Capitalization::Sentence,
+                "any" => Capitalization::Any,
+                _ => return Err(format!("unknown capitalization `{value}`")),
+            },
+            _ => return Err(format!("`{key}` has the wrong type")),
+        };
+        Ok(())
+    }
+
+    fn check(&self, tag: &Tag, parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        let (TagType::Core(Core::Heading), Some(TagType::Level(level))) =
+            (&tag.tag_type, parent.map(|parent| &parent.tag_type))
+        else {
+            return;
+        };
+        let Some((_, style)) = self.styles.iter().find(|(other, _)| other == level) else {
+            return;
+        };
+        let heading = tag.text();
+        if !style.matches(&heading) {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                format!(
+                    "the heading `{heading}` of a `{}` is not in {}",
+                    level.name(),
+                    style.name()
+                ),
+                tag.span(),
+            ));
+        }
+    }
+}
+
+struct MissingNum;
+
+impl Lint for MissingNum {
+    fn code(&self) -> &'static str {
+        "missing-num"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, tag: &Tag, _: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        // Special-purpose levels such as `compiledAct` are not numbered.
+        let TagType::Level(level) = tag.tag_type else {
+            return;
+        };
+        if level.rank().is_some()
+            && !tag
+                .children
+                .iter()
+                .any(|child| child.tag_type == TagType::Core(Core::Num))
+        {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                format!("the `{}` has no `num`", level.name()),
+                tag.span(),
+            ));
+        }
+    }
+}
+
+struct EmptyContent;
+
+impl Lint for EmptyContent {
+    fn code(&self) -> &'static str {
+        "empty-content"
+    }
+
+    fn check(&self, tag: &Tag, _: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        if tag.tag_type == TagType::Primitive(Primitive::Content) && tag.text().is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                "the `content` is empty",
+                tag.span(),
+            ));
+        }
+    }
+}
+
+struct NumTrailingWhitespace;
+
+impl Lint for NumTrailingWhitespace {
+    fn code(&self) -> &'static str {
+        "num-trailing-whitespace"
+    }
+
+    fn check(&self, tag: &Tag, _: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        if tag.tag_type != TagType::Core(Core::Num) {
+            return;
+        }
+        if let Some(num) = tag
+            .content
+            .as_deref()
+            .filter(|num| num.ends_with(char::is_whitespace))
+        {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                format!("the `num` `{}` ends with whitespace", num.trim_end()),
+                tag.span(),
+            ));
+        }
+    }
+}
+
+struct ChapeauWithoutSublevels;
+
+impl Lint for ChapeauWithoutSublevels {
+    fn code(&self) -> &'static str {
+        "chapeau-without-sublevels"
+    }
+
+    fn check(&self, tag: &Tag, parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        let Some(parent) = parent.filter(|_| tag.tag_type == TagType::Other(Other::Chapeau)) else {
+            return;
+        };
+        let following =
+            index_in(tag, parent).map_or(&[][..], |index| &parent.children[index + 1..]);
+        if !following.iter().any(is_level) {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                "the `chapeau` is not followed by any nested level",
+                tag.span(),
+            ));
+        }
+    }
+}
+
+struct ContinuationWithoutSublevels;
+
+impl Lint for ContinuationWithoutSublevels {
+    fn code(&self) -> &'static str {
+        "continuation-without-sublevels"
+    }
+
+    fn check(&self, tag: &Tag, parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        let Some(parent) = parent.filter(|_| tag.tag_type == TagType::Other(Other::Continuation))
+        else {
+            return;
+        };
+        let preceding = index_in(tag, parent).map_or(&[][..], |index| &parent.children[..index]);
+        if !preceding.iter().any(is_level) {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                "the `continuation` does not follow any nested level",
+                tag.span(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<chapter identifier="/us/usc/t5/ch5"><num value="5">CHAPTER 5.</num><heading>Administrative Procedure</heading>
+<section identifier="/us/usc/t5/s552"><num value="552">Sec. 552. </num><heading>PUBLIC INFORMATION</heading>
+<content></content>
+<continuation>as provided</continuation>
+<subsection identifier="/us/usc/t5/s552/a"><heading>in general</heading><chapeau>Each agency shall</chapeau><content>make available</content></subsection>
+</section>
+</chapter>
+</main>
+</uscDoc>"#;
+
+    fn messages(linter: &Linter) -> Vec<(&'static str, Severity, String)> {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+        linter
+            .lint(&uslm)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.severity, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_builtin_rules() {
+        let output = messages(&Linter::new());
+
+        assert_eq!(
+            output,
+            vec![
+                (
+                    "heading-capitalization",
+                    Severity::Warning,
+                    "the heading `Administrative Procedure` of a `chapter` is not in upper case"
+                        .to_string()
+                ),
+                (
+                    "num-trailing-whitespace",
+                    Severity::Warning,
+                    "the `num` `Sec. 552.` ends with whitespace".to_string()
+                ),
+                (
+                    "heading-capitalization",
+                    Severity::Warning,
+                    "the heading `PUBLIC INFORMATION` of a `section` is not in sentence case"
+                        .to_string()
+                ),
+                (
+                    "empty-content",
+                    Severity::Warning,
+                    "the `content` is empty".to_string()
+                ),
+                (
+                    "continuation-without-sublevels",
+                    Severity::Warning,
+                    "the `continuation` does not follow any nested level".to_string()
+                ),
+                (
+                    "missing-num",
+                    Severity::Error,
+                    "the `subsection` has no `num`".to_string()
+                ),
+                (
+                    "heading-capitalization",
+                    Severity::Warning,
+                    "the heading `in general` of a `subsection` is not in sentence case"
+                        .to_string()
+                ),
+                (
+                    "chapeau-without-sublevels",
+                    Severity::Warning,
+                    "the `chapeau` is not followed by any nested level".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_configured() {
+        let config = r#"
+# House style for the code
+[rules.heading-capitalization]
+severity = "error"
+chapter = "title"   # as printed in the table of contents
+section = 'any'
+
+[rules."chapeau-without-sublevels"]
+enabled = false
+
+[rules.empty-content]
+enabled = false
+[rules.missing-num]
+severity = "warning"
+"#;
+        let mut linter = Linter::new();
+
+        linter.configure(&config.parse().unwrap()).unwrap();
+
+        assert_eq!(
+            messages(&linter)
+                .into_iter()
+                .map(|(code, severity, _)| (code, severity))
+                .collect::<Vec<_>>(),
+            vec![
+                ("num-trailing-whitespace", Severity::Warning),
+                ("continuation-without-sublevels", Severity::Warning),
+                ("missing-num", Severity::Warning),
+                ("heading-capitalization", Severity::Error),
+            ]
+        );
+        assert_eq!(
+            linter.rules().collect::<Vec<_>>(),
+            vec![
+                ("heading-capitalization", Some(Severity::Error)),
+                ("missing-num", Some(Severity::Warning)),
+                ("empty-content", None),
+                ("num-trailing-whitespace", Some(Severity::Warning)),
+                ("chapeau-without-sublevels", None),
+                ("continuation-without-sublevels", Some(Severity::Warning)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_config_errors() {
+        let configure = |config: &str| {
+            config
+                .parse::<LintConfig>()
+                .and_then(|config| Linter::new().configure(&config))
+        };
+
+        assert_eq!(
+            configure("enabled = false"),
+            Err("line 1: option outside a `[rules.<name>]` table".to_string())
+        );
+        assert_eq!(
+            configure("[lints.missing-num]"),
+            Err("line 1: unknown table `lints.missing-num`".to_string())
+        );
+        assert_eq!(
+            configure("[rules.missing-num]\nseverity = \"fatal"),
+            Err("line 2: malformed string `\"fatal`".to_string())
+        );
+        assert_eq!(
+            configure("[rules.missing-heading]\nenabled = true"),
+            Err("unknown rule `missing-heading`".to_string())
+        );
+        assert_eq!(
+            configure("[rules.missing-num]\nseverity = \"fatal\""),
+            Err("`missing-num`: unknown severity `fatal`".to_string())
+        );
+        assert_eq!(
+            configure("[rules.heading-capitalization]\nchapter = \"small\""),
+            Err("`heading-capitalization`: unknown capitalization `small`".to_string())
+        );
+        assert_eq!(
+            configure("[rules.empty-content]\nlength = 1"),
+            Err("`empty-content`: unknown option `length`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lint_config_unsupported_toml() {
+        let parse = |config: &str| config.parse::<LintConfig>();
+
+        assert_eq!(
+            parse("rules.missing-num.enabled = false"),
+            Err("line 1: dotted key `rules.missing-num.enabled` is not supported, use a `[rules.<name>]` table".to_string())
+        );
+        assert_eq!(
+            parse("[rules.missing-num]\nseverity = { level = \"error\" }"),
+            Err("line 2: inline tables are not supported".to_string())
+        );
+        assert_eq!(
+            parse("[rules.heading-capitalization]\nsection = [\"title\"]"),
+            Err("line 2: arrays are not supported".to_string())
+        );
+        assert_eq!(
+            parse("[rules.missing-num]\nseverity = \"\"\"\nerror\"\"\""),
+            Err("line 2: multi-line strings are not supported".to_string())
+        );
+        assert_eq!(
+            parse("[[rules.missing-num]]"),
+            Err("line 1: arrays of tables are not supported".to_string())
+        );
+        assert_eq!(
+            parse("[rules.missing-num]\nlength = 1.5"),
+            Err("line 2: unsupported value `1.5`".to_string())
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use crate::{
+    html::{HrefResolver, IdentityResolver},
+    tags::{Core, Generic, Other, Primitive, Table, Tag, TagType},
+    Uslm,
+};
+
+/// Renders a USLM document as Markdown. Levels nested up to
+/// `heading_depth` deep become headings, deeper levels become nested
+/// list items.
+#[derive(Debug)]
+pub struct MarkdownRenderer<R = IdentityResolver> {
+    resolver: R,
+    heading_depth: usize,
+}
+
+impl MarkdownRenderer {
+    pub fn new() -> Self {
+        MarkdownRenderer {
+            resolver: IdentityResolver,
+            heading_depth: 6,
+        }
+    }
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        MarkdownRenderer::new()
+    }
+}
+
+impl<R: HrefResolver> MarkdownRenderer<R> {
+    pub fn with_resolver(resolver: R) -> Self {
+        MarkdownRenderer {
+            resolver,
+            heading_depth: 6,
+        }
+    }
+
+    /// Sets how many levels deep headings are used, capped at the six
+    /// heading ranks Markdown supports.
+    pub fn heading_depth(mut self, heading_depth: usize) -> Self {
+        self.heading_depth = heading_depth.min(6);
+        self
+    }
+
+    pub fn render(&self, uslm: &Uslm) -> String {
+        let mut out = String::new();
+        for tag in &uslm.content {
+            self.write_block(&mut out, tag, 0);
+        }
+        finish(out)
+    }
+
+    pub fn render_tag(&self, tag: &Tag) -> String {
+        let mut out = String::new();
+        self.write_block(&mut out, tag, 0);
+        finish(out)
+    }
+
+    /// Writes `tag` as a sequence of blank-line separated blocks.
+    /// `depth` counts the levels enclosing `tag`.
+    fn write_block(&self, out: &mut String, tag: &Tag, depth: usize) {
+        match &tag.tag_type {
+            TagType::Core(Core::Meta | Core::Toc) | TagType::Meta(_) | TagType::Property(_) => {}
+            TagType::Level(_) | TagType::Core(Core::Level) => self.write_level(out, tag, depth + 1),
+            TagType::Core(Core::QuotedContent) => {
+                let mut inner = String::new();
+                for child in &tag.children {
+                    self.write_block(&mut inner, child, depth);
+                }
+                if let Some(content) = &tag.content {
+                    inner.push_str(&escape_line_start(&escape(content.trim())));
+                }
+                for line in inner.trim_end().lines() {
+                    out.push_str(if line.is_empty() { ">" } else { "> " });
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            TagType::Table(Table::Table) => self.write_table(out, tag),
+            _ if is_inline_block(tag) => {
+                let text = self.inline(tag);
+                if !text.is_empty() {
+                    out.push_str(&escape_line_start(&text));
+                    out.push_str("\n\n");
+                }
+            }
+            _ => {
+                for child in &tag.children {
+                    self.write_block(out, child, depth);
+                }
+                if let Some(content) = &tag.content {
+                    out.push_str(&escape_line_start(&escape(content.trim())));
+                    out.push_str("\n\n");
+                }
+            }
+        }
+    }
+
+    fn write_level(&self, out: &mut String, tag: &Tag, depth: usize) {
+        let label = [TagType::Core(Core::Num), TagType::Core(Core::Heading)]
+            .iter()
+            .filter_map(|tag_type| {
+                tag.children
+                    .iter()
+                    .find(|child| &child.tag_type == tag_type)
+            })
+            .map(|child| self.inline(child))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let body = tag
+            .children
+            .iter()
+            .filter(|child| !matches!(child.tag_type, TagType::Core(Core::Num | Core::Heading)));
+
+        if depth <= self.heading_depth {
+            out.push_str(&"#".repeat(depth));
+            out.push(' ');
+            out.push_str(&label);
+            out.push_str("\n\n");
+            for child in body {
+                self.write_block(out, child, depth);
+            }
+            return;
+        }
+
+        // Text ahead of the first sub-level joins the list item's own
+        // line, anything after it is indented under the item.
+        let indent = "  ".repeat(depth - self.heading_depth - 1);
+        let mut line = label;
+        let mut nested = false;
+        let mut rest = String::new();
+        for child in body {
+            if is_level(child) {
+                nested = true;
+                self.write_level(&mut rest, child, depth + 1);
+            } else if !nested {
+                line = join(&[line, self.inline(child)]);
+            } else {
+                rest.push_str(&indent);
+                rest.push_str("  ");
+                rest.push_str(&escape_line_start(&self.inline(child)));
+                rest.push('\n');
+            }
+        }
+        out.push_str(&indent);
+        out.push_str("- ");
+        out.push_str(&escape_line_start(&line));
+        out.push('\n');
+        out.push_str(&rest);
+        if depth == self.heading_depth + 1 {
+            out.push('\n');
+        }
+    }
+
+    fn write_table(&self, out: &mut String, tag: &Tag) {
+        let mut rows = Vec::new();
+        collect_rows(tag, &mut rows);
+        let width = rows.iter().map(|row| row.children.len()).max().unwrap_or(0);
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells: Vec<String> = row
+                .children
+                .iter()
+                .map(|cell| self.inline(cell).replace('|', "\\|"))
+                .collect();
+            cells.resize(width, String::new());
+            out.push_str("| ");
+            out.push_str(&cells.join(" | "));
+            out.push_str(" |\n");
+            if index == 0 {
+                out.push('|');
+                out.push_str(&" --- |".repeat(width));
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+    }
+
+    /// Flattens `tag` into a single line of Markdown text.
+    fn inline(&self, tag: &Tag) -> String {
+        let mut parts: Vec<String> = tag
+            .children
+            .iter()
+            .map(|child| {
+                let text = self.inline(child);
+                match &child.tag_type {
+                    TagType::Generic(Generic::B) => format!("**{text}**"),
+                    TagType::Generic(Generic::I) => format!("*{text}*"),
+                    TagType::Core(Core::QuotedText) => format!("\"{text}\""),
+                    TagType::Core(Core::Ref) => match child.href() {
+                        Some(href) => format!("[{text}]({})", self.resolver.resolve(href)),
+                        None => text,
+                    },
+                    _ => text,
+                }
+            })
+            .collect();
+        if let Some(content) = &tag.content {
+            parts.push(escape(content.trim()));
+        }
+        join(&parts)
+    }
+}
+
+/// Whether every child of `tag` is an inline element, so that it can be
+/// written out as a single paragraph.
+fn is_inline_block(tag: &Tag) -> bool {
+    tag.children.iter().all(|child| {
+        matches!(
+            child.tag_type,
+            TagType::Generic(Generic::B | Generic::I)
+                | TagType::Core(Core::Ref | Core::QuotedText | Core::Date)
+                | TagType::Other(Other::Term | Other::Def)
+                | TagType::Primitive(Primitive::Inline | Primitive::Marker)
+        )
+    })
+}
+
+fn is_level(tag: &Tag) -> bool {
+    matches!(tag.tag_type, TagType::Level(_) | TagType::Core(Core::Level))
+}
+
+fn collect_rows<'a, 's>(tag: &'a Tag<'s>, rows: &mut Vec<&'a Tag<'s>>) {
+    for child in &tag.children {
+        match child.tag_type {
+            TagType::Table(Table::Tr) => rows.push(child),
+            TagType::Table(Table::Thead | Table::Tbody | Table::Tfoot) => collect_rows(child, rows),
+            _ => {}
+        }
+    }
+}
+
+/// Joins inline fragments with single spaces. Whitespace between
+/// elements is not kept by the parser, so none is assumed before
+/// trailing punctuation.
+fn join(parts: &[String]) -> String {
+    let mut out = String::new();
+    for part in parts.iter().filter(|part| !part.is_empty()) {
+        if !out.is_empty() && !part.starts_with(['.', ',', ';', ':', ')']) {
+            out.push(' ');
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Escapes the characters Markdown reads as emphasis, code, links or
+/// headings.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '#') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Keeps a line from starting a list or a block quote, as a `num` of "1."
+/// or "2)" would, or text starting with "-".
+fn escape_line_start(line: &str) -> String {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if (1..=9).contains(&digits) && line[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+    match line.starts_with(['-', '+', '>']) {
+        true => format!("\\{line}"),
+        false => line.to_string(),
+    }
+}
+
+fn finish(out: String) -> String {
+    let mut out = out.trim_end().to_string();
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<lawDoc identifier="/us/usc/t5">
+   <meta>
+      <docNumber>5</docNumber>
+   </meta>
+   <main>
+      <chapter>
+         <num value="1">CHAPTER 1.</num>
+         <heading>General Provisions</heading>
+         <section>
+            <num value="101">Sec. 101.</num>
+            <heading>Definitions</heading>
+            <chapeau>For the purposes of this title</chapeau>
+            <paragraph>
+               <num value="1">1.</num>
+               <content><b>Agency</b> means each authority of the Government</content>
+            </paragraph>
+            <paragraph>
+               <num value="2">2.</num>
+               <content><ref href="/us/usc/t5/s102">Section 102</ref> applies</content>
+               <subparagraph>
+                  <num value="A">A.</num>
+                  <content><i>Employee</i> has the meaning given</content>
+               </subparagraph>
+            </paragraph>
+         </section>
+      </chapter>
+   </main>
+</lawDoc>"#;
+
+    #[test]
+    fn test_render_headings() {
+        let mut input = INPUT;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = MarkdownRenderer::new().render(&uslm);
+
+        assert_eq!(
+            output,
+            r#"# CHAPTER 1. General Provisions
+
+## Sec. 101. Definitions
+
+For the purposes of this title
+
+### 1.
+
+**Agency** means each authority of the Government
+
+### 2.
+
+[Section 102](/us/usc/t5/s102) applies
+
+#### A.
+
+*Employee* has the meaning given
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_heading_depth_cutoff() {
+        let mut input = INPUT;
+
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = MarkdownRenderer::new().heading_depth(2).render(&uslm);
+
+        assert_eq!(
+            output,
+            r#"# CHAPTER 1. General Provisions
+
+## Sec. 101. Definitions
+
+For the purposes of this title
+
+- 1\. **Agency** means each authority of the Government
+
+- 2\. [Section 102](/us/usc/t5/s102) applies
+  - A. *Employee* has the meaning given
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_quoted_content() {
+        let mut input = r#"<quotedContent><section><num value="3">Sec. 3.</num><content>New text</content></section></quotedContent>"#;
+
+        let tags = crate::tags::parse(&mut input).unwrap();
+
+        let output = MarkdownRenderer::new().render_tag(&tags[0]);
+
+        assert_eq!(output, "> # Sec. 3.\n>\n> New text\n");
+    }
+
+    #[test]
+    fn test_render_table() {
+        let mut input = r#"<table><thead><tr><th>Agency</th><th>Code</th></tr></thead><tbody><tr><td>Labor</td><td>29</td></tr></tbody></table>"#;
+
+        let tags = crate::tags::parse(&mut input).unwrap();
+
+        let output = MarkdownRenderer::new().render_tag(&tags[0]);
+
+        assert_eq!(output, "| Agency | Code |\n| --- | --- |\n| Labor | 29 |\n");
+    }
+
+    #[test]
+    fn test_render_escapes_markdown() {
+        let mut input = r#"<section><num value="1">1.</num><content>text</content><paragraph><num value="1">1.</num><content>- none</content></paragraph></section>"#;
+        let mut tags = crate::tags::parse(&mut input).unwrap();
+        tags[0].children[1].content = Some("The *rate* under [section 2] of title_5 # 3".into());
+
+        let output = MarkdownRenderer::new()
+            .heading_depth(0)
+            .render_tag(&tags[0]);
+
+        assert_eq!(
+            output,
+            "- 1\\. The \\*rate\\* under \\[section 2\\] of title\\_5 \\# 3\n  - 1\\. - none\n"
+        );
+    }
+
+    #[test]
+    fn test_render_escapes_continuation() {
+        let mut input = r#"<section><num value="1">1.</num><paragraph><num value="a">(a)</num><content>text</content></paragraph><continuation>text</continuation><continuation>text</continuation></section>"#;
+        let mut tags = crate::tags::parse(&mut input).unwrap();
+        tags[0].children[2].content = Some("2. applies".into());
+        tags[0].children[3].content = Some("- none".into());
+
+        let output = MarkdownRenderer::new()
+            .heading_depth(0)
+            .render_tag(&tags[0]);
+
+        assert_eq!(output, "- 1\\.\n  - (a) text\n  2\\. applies\n  \\- none\n");
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use crate::{
+    attributes::Attribute,
+    tags::{Core, Note, Tag, TagType},
+    Uslm,
+};
+
+/// The notes attached to one provision, see [`Uslm::notes`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Notes<'a, 's> {
+    /// The `identifier` of the provision, `None` for notes outside any
+    /// identified provision.
+    pub provision: Option<&'s str>,
+    pub notes: Vec<NoteEntry<'a, 's>>,
+}
+
+impl<'a, 's> Notes<'a, 's> {
+    /// The notes on `topic`, in document order.
+    pub fn by_topic(&self, topic: Topic<'s>) -> impl Iterator<Item = &NoteEntry<'a, 's>> + '_ {
+        self.notes
+            .iter()
+            .filter(move |note| note.topic.as_ref() == Some(&topic))
+    }
+}
+
+/// A single note with its heading and body text.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoteEntry<'a, 's> {
+    pub kind: NoteKind,
+    /// From the `topic` attribute, or recognized from the heading when
+    /// there is none.
+    pub topic: Option<Topic<'s>>,
+    pub heading: Option<String>,
+    /// The text of the note without its heading.
+    pub body: String,
+    pub tag: &'a Tag<'s>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteKind {
+    Statutory,
+    Editorial,
+    Change,
+    /// A plain `note` element.
+    Note,
+}
+
+/// The subject of a note, with the topics common in the US Code as
+/// variants of their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Topic<'s> {
+    Amendments,
+    EffectiveDate,
+    ShortTitle,
+    ReferencesInText,
+    Codification,
+    Other(&'s str),
+}
+
+impl<'s> Topic<'s> {
+    /// Reads a `topic` attribute value, as in `effectiveDate`.
+    pub fn from_attribute(topic: &'s str) -> Self {
+        match topic {
+            "amendments" => Topic::Amendments,
+            "effectiveDate" => Topic::EffectiveDate,
+            "shortTitle" => Topic::ShortTitle,
+            "referencesInText" => Topic::ReferencesInText,
+            "codification" => Topic::Codification,
+            _ => Topic::Other(topic),
+        }
+    }
+
+    /// Recognizes a common topic from a heading, as in "Effective Date".
+    fn from_heading(heading: &str) -> Option<Self> {
+        let topic = match heading.trim_end_matches('.').to_lowercase().as_str() {
+            "amendments" => Topic::Amendments,
+            "effective date" => Topic::EffectiveDate,
+            "short title" => Topic::ShortTitle,
+            "references in text" => Topic::ReferencesInText,
+            "codification" => Topic::Codification,
+            _ => return None,
+        };
+        Some(topic)
+    }
+}
+
+impl<'s> Uslm<'s> {
+    /// The statutory, editorial, change and plain notes of the document,
+    /// grouped by the closest provision containing them, in document
+    /// order.
+    pub fn notes(&self) -> Vec<Notes<'_, 's>> {
+        let mut notes: Vec<Notes<'_, 's>> = Vec::new();
+        let mut groups = HashMap::new();
+        for tag in &self.content {
+            collect(tag, None, &mut notes, &mut groups);
+        }
+        notes
+    }
+}
+
+/// `groups` maps each provision to the index of its group in `notes`.
+fn collect<'a, 's>(
+    tag: &'a Tag<'s>,
+    provision: Option<&'s str>,
+    notes: &mut Vec<Notes<'a, 's>>,
+    groups: &mut HashMap<Option<&'s str>, usize>,
+) {
+    let kind = match tag.tag_type {
+        TagType::Note(Note::StatutoryNote) => Some(NoteKind::Statutory),
+        TagType::Note(Note::EditorialNote) => Some(NoteKind::Editorial),
+        TagType::Note(Note::ChangeNote) => Some(NoteKind::Change),
+        TagType::Core(Core::Note) => Some(NoteKind::Note),
+        _ => None,
+    };
+    if let Some(kind) = kind {
+        let entry = entry(tag, kind);
+        match groups.get(&provision) {
+            Some(&index) => notes[index].notes.push(entry),
+            None => {
+                groups.insert(provision, notes.len());
+                notes.push(Notes {
+                    provision,
+                    notes: vec![entry],
+                });
+            }
+        }
+        return;
+    }
+
+    let provision = match tag.tag_type {
+        TagType::Level(_) => tag.identifier().or(provision),
+        _ => provision,
+    };
+    for child in &tag.children {
+        collect(child, provision, notes, groups);
+    }
+}
+
+fn entry<'a, 's>(tag: &'a Tag<'s>, kind: NoteKind) -> NoteEntry<'a, 's> {
+    let heading = tag
+        .children
+        .iter()
+        .find(|child| child.tag_type == TagType::Core(Core::Heading))
+        .map(Tag::text);
+    let topic = tag
+        .attributes
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Topic(topic) => Some(Topic::from_attribute(topic)),
+            _ => None,
+        })
+        .or_else(|| heading.as_deref().and_then(Topic::from_heading));
+    let mut body: Vec<String> = tag
+        .children
+        .iter()
+        .filter(|child| !matches!(child.tag_type, TagType::Core(Core::Heading | Core::Num)))
+        .map(Tag::text)
+        .filter(|text| !text.is_empty())
+        .collect();
+    if let Some(content) = &tag.content {
+        let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !content.is_empty() {
+            body.push(content);
+        }
+    }
+    NoteEntry {
+        kind,
+        topic,
+        heading,
+        body: body.join(" "),
+        tag,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<section identifier="/us/usc/t5/s552">
+<num value="552">Sec. 552.</num>
+<notes>
+<editorialNote topic="amendments"><heading>Amendments</heading><content>2016–Subsec. a. Pub. L. 114–185 amended subsec. a generally.</content></editorialNote>
+<statutoryNote topic="effectiveDate"><heading>Effective Date of 2016 Amendment</heading><content>Amendment by Pub. L. 114–185 effective on the date of enactment.</content></statutoryNote>
+<statutoryNote><heading>Short Title</heading><content>This section may be cited as the Freedom of Information Act.</content></statutoryNote>
+<note topic="miscellaneous"><heading>Transfer of Functions</heading></note>
+</notes>
+</section>
+<section identifier="/us/usc/t5/s553">
+<notes><changeNote>Section was editorially reclassified</changeNote></notes>
+</section>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_notes() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.notes();
+
+        let summary: Vec<_> = output
+            .iter()
+            .map(|notes| {
+                (
+                    notes.provision,
+                    notes
+                        .notes
+                        .iter()
+                        .map(|note| (note.kind, note.topic.clone(), note.heading.as_deref()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Some("/us/usc/t5/s552"),
+                    vec![
+                        (
+                            NoteKind::Editorial,
+                            Some(Topic::Amendments),
+                            Some("Amendments")
+                        ),
+                        (
+                            NoteKind::Statutory,
+                            Some(Topic::EffectiveDate),
+                            Some("Effective Date of 2016 Amendment")
+                        ),
+                        (
+                            NoteKind::Statutory,
+                            Some(Topic::ShortTitle),
+                            Some("Short Title")
+                        ),
+                        (
+                            NoteKind::Note,
+                            Some(Topic::Other("miscellaneous")),
+                            Some("Transfer of Functions")
+                        ),
+                    ]
+                ),
+                (
+                    Some("/us/usc/t5/s553"),
+                    vec![(NoteKind::Change, None, None)]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notes_by_topic() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.notes();
+
+        let bodies: Vec<_> = output[0]
+            .by_topic(Topic::ShortTitle)
+            .map(|note| note.body.as_str())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec!["This section may be cited as the Freedom of Information Act."]
+        );
+        assert_eq!(
+            output[1].notes[0].body,
+            "Section was editorially reclassified"
+        );
+    }
+}
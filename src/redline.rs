@@ -0,0 +1,391 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    diff::{align, words, WordDiff},
+    tags::{Core, Tag, TagType},
+    Uslm,
+};
+
+/// Renders a comparative print of two versions of a document: every
+/// provision of the newer version in its place in the hierarchy, with
+/// struck text, inserted text and moved provisions marked. Deleted
+/// provisions are shown where they used to be, and so is a marker for
+/// each moved provision.
+#[derive(Debug, Default)]
+pub struct RedlineRenderer;
+
+impl RedlineRenderer {
+    pub fn new() -> Self {
+        RedlineRenderer
+    }
+
+    /// Renders the redline as an HTML fragment, with `del` for struck and
+    /// `ins` for inserted text, and one `section` per provision classed
+    /// with its USLM element name and `inserted`, `deleted` or `moved`.
+    /// The old place of a moved provision is an empty `section` classed
+    /// `moved-away`.
+    pub fn render_html(&self, old: &Uslm, new: &Uslm) -> String {
+        let redline = Redline::new(old, new);
+        let mut out = String::from("<div class=\"redline\">");
+        for root in &redline.roots {
+            write_html(&mut out, &redline.nodes, *root);
+        }
+        out.push_str("</div>\n");
+        out
+    }
+
+    /// Renders the redline for a terminal, one provision per line indented
+    /// by depth, with struck text in red strikethrough and inserted text
+    /// underlined in green.
+    pub fn render_ansi(&self, old: &Uslm, new: &Uslm) -> String {
+        let redline = Redline::new(old, new);
+        let mut out = String::new();
+        for root in &redline.roots {
+            write_ansi(&mut out, &redline.nodes, *root, 0);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Kept,
+    Inserted,
+    Deleted,
+    Moved,
+    /// Where a moved provision used to be.
+    MovedAway,
+}
+
+struct Node {
+    name: &'static str,
+    state: State,
+    num: Vec<WordDiff>,
+    text: Vec<WordDiff>,
+    children: Vec<usize>,
+}
+
+/// The provisions of both versions merged into one tree, kept as an
+/// arena indexed by position.
+struct Redline {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+}
+
+impl Redline {
+    fn new(old: &Uslm, new: &Uslm) -> Self {
+        let alignment = align(old, new);
+
+        // The provisions of `new` take the first indices, in document
+        // order. The deleted provisions of `old` and the old places of the
+        // moved ones follow.
+        let mut nodes = Vec::new();
+        let mut parents = Vec::new();
+        for (index, provision) in alignment.new.iter().enumerate() {
+            let (state, num, text) = match alignment.pairs[index] {
+                Some(counterpart) => {
+                    let counterpart = &alignment.old[counterpart];
+                    let state = match alignment.moved(index) {
+                        true => State::Moved,
+                        false => State::Kept,
+                    };
+                    (
+                        state,
+                        words(&num(counterpart.tag), &num(provision.tag)),
+                        words(&counterpart.text, &provision.text),
+                    )
+                }
+                None => (
+                    State::Inserted,
+                    inserted(num(provision.tag)),
+                    inserted(provision.text.clone()),
+                ),
+            };
+            nodes.push(Node {
+                name: provision.tag.tag_type.name(),
+                state,
+                num,
+                text,
+                children: Vec::new(),
+            });
+            parents.push(provision.parent);
+        }
+
+        let mut roots = Vec::new();
+        for (index, parent) in parents.into_iter().enumerate() {
+            match parent {
+                Some(parent) => nodes[parent].children.push(index),
+                None => roots.push(index),
+            }
+        }
+
+        // For each provision of `old`, the node its children go into and
+        // the node standing in its old place.
+        let mut merged = vec![None; alignment.old.len()];
+        let mut here = vec![None; alignment.old.len()];
+        for (index, pair) in alignment.pairs.iter().enumerate() {
+            if let Some(old_index) = pair {
+                merged[*old_index] = Some(index);
+                here[*old_index] = Some(index);
+            }
+        }
+        // The previous provision of `old` with the same parent.
+        let mut previous = vec![None; alignment.old.len()];
+        let mut last = HashMap::new();
+        for (old_index, provision) in alignment.old.iter().enumerate() {
+            previous[old_index] = last.insert(provision.parent, old_index);
+        }
+
+        for (old_index, provision) in alignment.old.iter().enumerate() {
+            let state = match merged[old_index] {
+                None => State::Deleted,
+                Some(index) if alignment.moved(index) => State::MovedAway,
+                Some(_) => continue,
+            };
+            let node = nodes.len();
+            nodes.push(match state {
+                State::Deleted => Node {
+                    name: provision.tag.tag_type.name(),
+                    state,
+                    num: deleted(num(provision.tag)),
+                    text: deleted(provision.text.clone()),
+                    children: Vec::new(),
+                },
+                _ => Node {
+                    name: provision.tag.tag_type.name(),
+                    state,
+                    num: kept(num(provision.tag)),
+                    text: Vec::new(),
+                    children: Vec::new(),
+                },
+            });
+            here[old_index] = Some(node);
+            if state == State::Deleted {
+                merged[old_index] = Some(node);
+            }
+
+            // A parent comes before its children, so it has a node by now,
+            // wherever it ended up.
+            let siblings = match provision.parent.and_then(|parent| merged[parent]) {
+                Some(parent) => &mut nodes[parent].children,
+                None => &mut roots,
+            };
+            // Right after the closest earlier sibling still in the same
+            // place, or first.
+            let mut earlier = previous[old_index];
+            let mut at = 0;
+            while let Some(sibling) = earlier {
+                let position =
+                    here[sibling].and_then(|node| siblings.iter().position(|child| *child == node));
+                if let Some(position) = position {
+                    at = position + 1;
+                    break;
+                }
+                earlier = previous[sibling];
+            }
+            siblings.insert(at, node);
+        }
+        Redline { nodes, roots }
+    }
+}
+
+/// The text of the provision's `num`, as in "Sec. 2.".
+fn num(tag: &Tag) -> String {
+    tag.children
+        .iter()
+        .find(|child| child.tag_type == TagType::Core(Core::Num))
+        .map(Tag::text)
+        .unwrap_or_default()
+}
+
+fn inserted(text: String) -> Vec<WordDiff> {
+    match text.is_empty() {
+        true => Vec::new(),
+        false => vec![WordDiff::Inserted(text)],
+    }
+}
+
+fn kept(text: String) -> Vec<WordDiff> {
+    match text.is_empty() {
+        true => Vec::new(),
+        false => vec![WordDiff::Equal(text)],
+    }
+}
+
+fn deleted(text: String) -> Vec<WordDiff> {
+    match text.is_empty() {
+        true => Vec::new(),
+        false => vec![WordDiff::Deleted(text)],
+    }
+}
+
+fn write_html(out: &mut String, nodes: &[Node], index: usize) {
+    let node = &nodes[index];
+    let state = match node.state {
+        State::Kept => "",
+        State::Inserted => " inserted",
+        State::Deleted => " deleted",
+        State::Moved => " moved",
+        State::MovedAway => " moved-away",
+    };
+    let _ = write!(out, "<section class=\"{}{state}\">", node.name);
+    if !node.num.is_empty() {
+        out.push_str("<span class=\"num\">");
+        write_html_words(out, &node.num);
+        out.push_str("</span>");
+    }
+    if !node.text.is_empty() {
+        out.push_str("<p>");
+        write_html_words(out, &node.text);
+        out.push_str("</p>");
+    }
+    for child in &node.children {
+        write_html(out, nodes, *child);
+    }
+    out.push_str("</section>");
+}
+
+fn write_html_words(out: &mut String, words: &[WordDiff]) {
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        // Text is still XML-escaped, which is valid HTML as-is.
+        let _ = match word {
+            WordDiff::Equal(text) => write!(out, "{text}"),
+            WordDiff::Inserted(text) => write!(out, "<ins>{text}</ins>"),
+            WordDiff::Deleted(text) => write!(out, "<del>{text}</del>"),
+        };
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const STRUCK: &str = "\x1b[9;31m";
+const UNDERLINED: &str = "\x1b[4;32m";
+const DIM: &str = "\x1b[2m";
+
+fn write_ansi(out: &mut String, nodes: &[Node], index: usize, depth: usize) {
+    let node = &nodes[index];
+    out.push_str(&"  ".repeat(depth));
+    write_ansi_words(out, node.num.iter().chain(&node.text));
+    match node.state {
+        State::Moved => {
+            let _ = write!(out, " {DIM}[moved]{RESET}");
+        }
+        State::MovedAway => {
+            let _ = write!(out, " {DIM}[moved away]{RESET}");
+        }
+        _ => {}
+    }
+    out.push('\n');
+    for child in &node.children {
+        write_ansi(out, nodes, *child, depth + 1);
+    }
+}
+
+fn write_ansi_words<'a>(out: &mut String, words: impl Iterator<Item = &'a WordDiff>) {
+    for (index, word) in words.enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        let _ = match word {
+            WordDiff::Equal(text) => write!(out, "{text}"),
+            WordDiff::Inserted(text) => write!(out, "{UNDERLINED}{text}{RESET}"),
+            WordDiff::Deleted(text) => write!(out, "{STRUCK}{text}{RESET}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const OLD: &str = r#"<bill><main>
+<section identifier="/us/bill/118/hr/1/s1"><num value="1">Sec. 1.</num><heading>Short title</heading>
+<subsection identifier="/us/bill/118/hr/1/s1/a"><num value="a">a.</num><content>This Act may be cited as the Records Act</content></subsection>
+<subsection identifier="/us/bill/118/hr/1/s1/b"><num value="b">b.</num><content>Repealed provisions</content></subsection>
+</section>
+</main></bill>"#;
+
+    const NEW: &str = r#"<bill><main>
+<section identifier="/us/bill/118/hr/1/s1"><num value="1">Sec. 1.</num><heading>Short title</heading>
+<subsection identifier="/us/bill/118/hr/1/s1/a"><num value="a">a.</num><content>This Act may be cited as the Public Records Act</content></subsection>
+</section>
+<section identifier="/us/bill/118/hr/1/s2"><num value="2">Sec. 2.</num><heading>Definitions</heading></section>
+</main></bill>"#;
+
+    fn parse(input: &str) -> Uslm<'_> {
+        let mut input = input;
+        Uslm::parse(&mut input).unwrap()
+    }
+
+    #[test]
+    fn test_render_redline_html() {
+        let old = parse(OLD);
+        let new = parse(NEW);
+
+        let output = RedlineRenderer::new().render_html(&old, &new);
+
+        assert_eq!(
+            output,
+            concat!(
+                "<div class=\"redline\">",
+                "<section class=\"section\"><span class=\"num\">Sec. 1.</span><p>Short title</p>",
+                "<section class=\"subsection\"><span class=\"num\">a.</span>",
+                "<p>This Act may be cited as the <ins>Public</ins> Records Act</p></section>",
+                "<section class=\"subsection deleted\"><span class=\"num\"><del>b.</del></span>",
+                "<p><del>Repealed provisions</del></p></section>",
+                "</section>",
+                "<section class=\"section inserted\"><span class=\"num\"><ins>Sec. 2.</ins></span>",
+                "<p><ins>Definitions</ins></p></section>",
+                "</div>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_redline_ansi() {
+        let old = parse(
+            r#"<uscDoc><main><chapter identifier="/us/usc/t5/ch5"><num value="5">CHAPTER 5.</num><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num></section></chapter><chapter identifier="/us/usc/t5/ch7"><num value="7">CHAPTER 7.</num></chapter></main></uscDoc>"#,
+        );
+        let new = parse(
+            r#"<uscDoc><main><chapter identifier="/us/usc/t5/ch5"><num value="5">CHAPTER 5.</num></chapter><chapter identifier="/us/usc/t5/ch7"><num value="7">CHAPTER 7.</num><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552a.</num></section></chapter></main></uscDoc>"#,
+        );
+
+        let output = RedlineRenderer::new().render_ansi(&old, &new);
+
+        assert_eq!(
+            output,
+            concat!(
+                "CHAPTER 5.\n",
+                "  Sec. 552. \x1b[2m[moved away]\x1b[0m\n",
+                "CHAPTER 7.\n",
+                "  Sec. \x1b[9;31m552.\x1b[0m \x1b[4;32m552a.\x1b[0m \x1b[2m[moved]\x1b[0m\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_redline_deleted_in_place() {
+        let old = parse(
+            r#"<uscDoc><main><section identifier="/us/usc/t5/s551"><num value="551">Sec. 551.</num></section><section identifier="/us/usc/t5/s552"><num value="552">Sec. 552.</num><subsection identifier="/us/usc/t5/s552/a"><num value="a">a.</num></subsection></section><section identifier="/us/usc/t5/s553"><num value="553">Sec. 553.</num></section></main></uscDoc>"#,
+        );
+        let new = parse(
+            r#"<uscDoc><main><section identifier="/us/usc/t5/s551"><num value="551">Sec. 551.</num></section><section identifier="/us/usc/t5/s553"><num value="553">Sec. 553.</num></section></main></uscDoc>"#,
+        );
+
+        let output = RedlineRenderer::new().render_ansi(&old, &new);
+
+        assert_eq!(
+            output,
+            concat!(
+                "Sec. 551.\n",
+                "\x1b[9;31mSec. 552.\x1b[0m\n",
+                "  \x1b[9;31ma.\x1b[0m\n",
+                "Sec. 553.\n",
+            )
+        );
+    }
+}
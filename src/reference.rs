@@ -0,0 +1,335 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Write},
+};
+
+use crate::{
+    attributes::Attribute,
+    common::escape,
+    span::Span,
+    tags::{Core, Tag, TagType},
+    Uslm,
+};
+
+/// A USLM reference such as `/us/usc/t5/s552/a`, split into the
+/// jurisdiction, the document and the path of levels inside it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UslmRef<'s> {
+    pub href: &'s str,
+    /// `us` for federal documents.
+    pub jurisdiction: &'s str,
+    /// The kind of document, such as `usc`, `pl` or `stat`.
+    pub document: &'s str,
+    /// One entry per level, outermost first, as in `["t5", "s552", "a"]`.
+    pub path: Vec<&'s str>,
+}
+
+impl<'s> UslmRef<'s> {
+    /// Splits an absolute `href`. Relative references and URLs to other
+    /// sites are not USLM references and give `None`.
+    pub fn parse(href: &'s str) -> Option<Self> {
+        let mut parts = href.strip_prefix('/')?.split('/');
+        let jurisdiction = parts.next().filter(|part| !part.is_empty())?;
+        let document = parts.next().filter(|part| !part.is_empty())?;
+        let path = parts.collect::<Vec<_>>();
+        if path.iter().any(|part| part.is_empty()) {
+            return None;
+        }
+        Some(UslmRef {
+            href,
+            jurisdiction,
+            document,
+            path,
+        })
+    }
+
+    /// The designation of the innermost level, as in `a` for
+    /// `/us/usc/t5/s552/a`.
+    pub fn last(&self) -> Option<&'s str> {
+        self.path.last().copied()
+    }
+}
+
+impl fmt::Display for UslmRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.href)
+    }
+}
+
+/// An outbound `ref` of a document, see [`Uslm::references`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reference<'s> {
+    /// The `identifier` of the closest element containing the `ref`.
+    pub source: Option<&'s str>,
+    pub target: UslmRef<'s>,
+    pub text: String,
+    pub span: Span,
+}
+
+impl<'s> Uslm<'s> {
+    /// Every `ref` whose `href` is a USLM reference, in document order.
+    /// Links to other sites and `idref`s within the document are left out.
+    pub fn references(&self) -> Vec<Reference<'s>> {
+        let mut references = Vec::new();
+        for tag in &self.content {
+            collect_references(tag, None, &mut references);
+        }
+        references
+    }
+}
+
+fn collect_references<'s>(
+    tag: &Tag<'s>,
+    source: Option<&'s str>,
+    references: &mut Vec<Reference<'s>>,
+) {
+    let mut source = source;
+    for attribute in &tag.attributes {
+        match attribute {
+            Attribute::Identifier(identifier) => source = Some(identifier),
+            Attribute::Href(href) if tag.tag_type == TagType::Core(Core::Ref) => {
+                if let Some(target) = UslmRef::parse(href) {
+                    references.push(Reference {
+                        source,
+                        target,
+                        text: tag.text(),
+                        span: tag.span(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    for child in &tag.children {
+        collect_references(child, source, references);
+    }
+}
+
+/// Citations between provisions across any number of documents, keyed by
+/// identifier.
+#[derive(Debug, Default)]
+pub struct ReferenceGraph {
+    nodes: Vec<String>,
+    indices: HashMap<String, usize>,
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl ReferenceGraph {
+    pub fn new() -> Self {
+        ReferenceGraph::default()
+    }
+
+    /// Adds the references of a document. References outside any element
+    /// with an `identifier` have no source and are skipped.
+    pub fn add(&mut self, uslm: &Uslm) {
+        for reference in uslm.references() {
+            if let Some(source) = reference.source {
+                let source = self.node(source);
+                let target = self.node(reference.target.href);
+                self.edges.insert((source, target));
+            }
+        }
+    }
+
+    fn node(&mut self, identifier: &str) -> usize {
+        if let Some(index) = self.indices.get(identifier) {
+            return *index;
+        }
+        self.nodes.push(identifier.to_string());
+        self.indices
+            .insert(identifier.to_string(), self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// The provisions citing `target` or any provision nested in it, so
+    /// that citations of `/us/usc/t42/s1983/a` count for
+    /// `/us/usc/t42/s1983`.
+    pub fn cited_by(&self, target: &str) -> Vec<&str> {
+        let within = |node: &str| {
+            node.strip_prefix(target)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+        let sources: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter(|(_, cited)| within(&self.nodes[*cited]))
+            .map(|(source, _)| *source)
+            .collect();
+        sources
+            .into_iter()
+            .map(|source| self.nodes[source].as_str())
+            .collect()
+    }
+
+    /// The provisions `source` cites.
+    pub fn cites(&self, source: &str) -> Vec<&str> {
+        let Some(source) = self.indices.get(source) else {
+            return Vec::new();
+        };
+        self.edges
+            .iter()
+            .filter(|(citing, _)| citing == source)
+            .map(|(_, cited)| self.nodes[*cited].as_str())
+            .collect()
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "<key id=\"identifier\" for=\"node\" attr.name=\"identifier\" attr.type=\"string\"/>\n",
+            "<graph id=\"references\" edgedefault=\"directed\">\n"
+        ));
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<node id=\"n{index}\"><data key=\"identifier\">{}</data></node>",
+                escape(node)
+            );
+        }
+        for (source, target) in &self.edges {
+            let _ = writeln!(out, "<edge source=\"n{source}\" target=\"n{target}\"/>");
+        }
+        out.push_str("</graph>\n</graphml>\n");
+        out
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph references {\n");
+        for (source, target) in &self.edges {
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\";",
+                dot_escape(&self.nodes[*source]),
+                dot_escape(&self.nodes[*target])
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Escapes text for a quoted DOT identifier.
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_uslm_ref() {
+        let output = UslmRef::parse("/us/usc/t5/s552/a");
+
+        assert_eq!(
+            output,
+            Some(UslmRef {
+                href: "/us/usc/t5/s552/a",
+                jurisdiction: "us",
+                document: "usc",
+                path: vec!["t5", "s552", "a"],
+            })
+        );
+        assert_eq!(output.unwrap().last(), Some("a"));
+    }
+
+    #[test]
+    fn test_parse_invalid_uslm_ref() {
+        assert_eq!(UslmRef::parse("us/usc/t5"), None);
+        assert_eq!(UslmRef::parse("/us"), None);
+        assert_eq!(UslmRef::parse("/us/usc//s552"), None);
+    }
+
+    const TITLE_42: &str = r#"<uscDoc identifier="/us/usc/t42">
+<main>
+<section identifier="/us/usc/t42/s1983"><num value="1983">Sec. 1983.</num><content>Every person who</content></section>
+<section identifier="/us/usc/t42/s1988">
+<num value="1988">Sec. 1988.</num>
+<subsection identifier="/us/usc/t42/s1988/b"><content><ref href="/us/usc/t42/s1983">section 1983 of this title</ref></content></subsection>
+<subsection identifier="/us/usc/t42/s1988/c"><content><ref href="https://www.congress.gov">Congress</ref></content></subsection>
+</section>
+</main>
+</uscDoc>"#;
+
+    const TITLE_28: &str = r#"<uscDoc identifier="/us/usc/t28">
+<main>
+<section identifier="/us/usc/t28/s1343"><content><ref href="/us/usc/t42/s1983/a">section 1983 of title 42</ref></content></section>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_references() {
+        let mut input = TITLE_42;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.references();
+
+        let start = TITLE_42.find("<ref").unwrap();
+        let end = TITLE_42.find("</ref>").unwrap() + "</ref>".len();
+        assert_eq!(
+            output,
+            vec![Reference {
+                source: Some("/us/usc/t42/s1988/b"),
+                target: UslmRef::parse("/us/usc/t42/s1983").unwrap(),
+                text: "section 1983 of this title".to_string(),
+                span: Span::new(start, end),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reference_graph() {
+        let mut title_42 = TITLE_42;
+        let mut title_28 = TITLE_28;
+        let mut graph = ReferenceGraph::new();
+
+        graph.add(&Uslm::parse(&mut title_42).unwrap());
+        graph.add(&Uslm::parse(&mut title_28).unwrap());
+
+        assert_eq!(
+            graph.cited_by("/us/usc/t42/s1983"),
+            vec!["/us/usc/t42/s1988/b", "/us/usc/t28/s1343"]
+        );
+        assert_eq!(graph.cited_by("/us/usc/t42/s198"), Vec::<&str>::new());
+        assert_eq!(
+            graph.cites("/us/usc/t28/s1343"),
+            vec!["/us/usc/t42/s1983/a"]
+        );
+        assert_eq!(
+            graph.to_dot(),
+            concat!(
+                "digraph references {\n",
+                "  \"/us/usc/t42/s1988/b\" -> \"/us/usc/t42/s1983\";\n",
+                "  \"/us/usc/t28/s1343\" -> \"/us/usc/t42/s1983/a\";\n",
+                "}\n"
+            )
+        );
+        assert!(graph
+            .to_graphml()
+            .contains("<edge source=\"n2\" target=\"n3\"/>"));
+    }
+
+    #[test]
+    fn test_reference_graph_escaping() {
+        let mut input = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<section identifier="/us/usc/t5/s552&amp;s553"><content><ref href="/us/usc/t5/s554">section 554</ref></content></section>
+</main>
+</uscDoc>"#;
+        let mut graph = ReferenceGraph::new();
+
+        graph.add(&Uslm::parse(&mut input).unwrap());
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains("<data key=\"identifier\">/us/usc/t5/s552&amp;s553</data>"));
+        assert!(!graphml.contains("&amp;amp;"));
+        assert_eq!(
+            graph.to_dot(),
+            "digraph references {\n  \"/us/usc/t5/s552&amp;s553\" -> \"/us/usc/t5/s554\";\n}\n"
+        );
+        assert_eq!(dot_escape(r#"a"b\c"#), r#"a\"b\\c"#);
+    }
+}
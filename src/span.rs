@@ -0,0 +1,111 @@
+/// Byte range of a node within the source document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Line and column of the start of the span. Scans `source` on every
+    /// call, use a [`LineIndex`] when looking up many spans.
+    pub fn start_position(&self, source: &str) -> Position {
+        LineIndex::new(source).position(self.start)
+    }
+}
+
+/// Where each part of a tag came from in the source document.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Spans {
+    /// From the `<` of the opening tag to the `>` of the closing tag.
+    pub element: Span,
+    /// The text content, when the tag has any.
+    pub content: Option<Span>,
+    /// One span per attribute, covering `key="value"`.
+    pub attributes: Vec<Span>,
+}
+
+/// A 1-based line and column, the column counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Byte offsets of the line starts in a source, for turning byte
+/// offsets into positions.
+#[derive(Debug)]
+pub struct LineIndex<'s> {
+    source: &'s str,
+    line_starts: Vec<usize>,
+}
+
+impl<'s> LineIndex<'s> {
+    pub fn new(source: &'s str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        LineIndex {
+            source,
+            line_starts,
+        }
+    }
+
+    /// The position of the byte `offset`, clamped to the end of the source.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let column = self.source[line_start..]
+            .char_indices()
+            .take_while(|(index, _)| line_start + index < offset)
+            .count();
+        Position {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index_position() {
+        let input = "<bill>\n  <meta>–</meta>\n</bill>";
+
+        let index = LineIndex::new(input);
+
+        assert_eq!(index.position(0), Position { line: 1, column: 1 });
+        assert_eq!(index.position(9), Position { line: 2, column: 3 });
+        assert_eq!(
+            index.position(18),
+            Position {
+                line: 2,
+                column: 10
+            }
+        );
+        assert_eq!(index.position(26), Position { line: 3, column: 1 });
+    }
+
+    #[test]
+    fn test_span_start_position() {
+        let input = "<bill>\n<meta></meta>\n</bill>";
+
+        let output = Span::new(7, 20).start_position(input);
+
+        assert_eq!(output, Position { line: 2, column: 1 });
+    }
+}
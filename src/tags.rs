@@ -1,64 +1,197 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{borrow::Cow, cmp::Ordering, fmt::Debug, str::FromStr};
 
 use winnow::{
     combinator::{alt, delimited, dispatch, fail, opt, peek, preceded, repeat},
-    stream::AsChar,
+    stream::{AsChar, Offset},
     token::{any, take_while},
     ModalResult, Parser,
 };
 
 use crate::{
-    attributes::{Attribute, VecExt},
-    common::{content, kvs, ws},
+    attributes::{kind, Attribute, AttributeKind, Attributes},
+    common::{content, invalid, kvs, ws},
+    span::{Span, Spans},
 };
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Tag<'s> {
     pub tag_type: TagType,
-    pub attributes: Vec<Attribute<'s>>,
-    pub content: Option<&'s str>,
+    pub attributes: Attributes<'s>,
+    pub content: Option<Cow<'s, str>>,
     pub children: Vec<Tag<'s>>,
+    pub spans: Spans,
 }
 
+/// Tags compare by structure alone, so trees parsed from different
+/// sources are equal when their markup is.
+impl PartialEq for Tag<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag_type == other.tag_type
+            && self.attributes == other.attributes
+            && self.content == other.content
+            && self.children == other.children
+    }
+}
+
+impl Eq for Tag<'_> {}
+
+impl<'s> Tag<'s> {
+    /// The value of the attribute of kind `A`, as in
+    /// `tag.attr::<kind::ColSpan>()`.
+    pub fn attr<A: AttributeKind<'s>>(&self) -> Option<&A::Value> {
+        self.attributes.get::<A>()
+    }
+
+    pub fn attr_mut<A: AttributeKind<'s>>(&mut self) -> Option<&mut A::Value> {
+        self.attributes.get_mut::<A>()
+    }
+
+    pub fn id(&self) -> Option<&'s str> {
+        self.attr::<kind::Id>().copied()
+    }
+
+    pub fn identifier(&self) -> Option<&'s str> {
+        self.attr::<kind::Identifier>().copied()
+    }
+
+    pub fn href(&self) -> Option<&'s str> {
+        self.attr::<kind::Href>().copied()
+    }
+
+    pub fn role(&self) -> Option<&'s str> {
+        self.attr::<kind::Role>().copied()
+    }
+
+    /// Replaces the attribute of the same kind, returning it, or adds
+    /// `attribute` after the others.
+    pub fn set_attr(&mut self, attribute: Attribute<'s>) -> Option<Attribute<'s>> {
+        let added = self.attributes.len();
+        let replaced = self.attributes.set(attribute);
+        // Added attributes have no place in the source.
+        if replaced.is_none() && self.spans.attributes.len() == added {
+            self.spans.attributes.push(Span::default());
+        }
+        replaced
+    }
+
+    pub fn remove_attr<A: AttributeKind<'s>>(&mut self) -> Option<Attribute<'s>> {
+        let (position, attribute) = self.attributes.remove::<A>()?;
+        if position < self.spans.attributes.len() {
+            self.spans.attributes.remove(position);
+        }
+        Some(attribute)
+    }
+
+    pub fn set_id(&mut self, id: &'s str) {
+        self.set_attr(Attribute::Id(id));
+    }
+
+    pub fn set_identifier(&mut self, identifier: &'s str) {
+        self.set_attr(Attribute::Identifier(identifier));
+    }
+
+    pub fn set_href(&mut self, href: &'s str) {
+        self.set_attr(Attribute::Href(href));
+    }
+
+    pub fn set_role(&mut self, role: &'s str) {
+        self.set_attr(Attribute::Role(role));
+    }
+}
+
+impl Tag<'_> {
+    /// Where the tag starts and ends in the source document.
+    pub fn span(&self) -> Span {
+        self.spans.element
+    }
+
+    /// The text of the tag and its descendants, with each run of text
+    /// separated by a single space.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.push_text(&mut text);
+        text
+    }
+
+    fn push_text(&self, text: &mut String) {
+        for child in &self.children {
+            child.push_text(text);
+        }
+        if let Some(content) = &self.content {
+            let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !content.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&content);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
 pub fn parse<'s>(input: &mut &'s str) -> ModalResult<Vec<Tag<'s>>> {
-    let tags: Vec<Tag<'s>> = repeat(0.., delimited(ws, tag, ws)).parse_next(input)?;
+    let source = *input;
+    parse_in(source, input)
+}
+
+/// Parses the tags at the start of `input`, which must be a suffix of
+/// `source`, with spans measured from the start of `source`.
+pub(crate) fn parse_in<'s>(source: &'s str, input: &mut &'s str) -> ModalResult<Vec<Tag<'s>>> {
+    let tags: Vec<Tag<'s>> = repeat(
+        0..,
+        delimited(ws, |input: &mut &'s str| tag(source, input), ws),
+    )
+    .parse_next(input)?;
     Ok(tags)
 }
 
-fn tag<'s>(input: &mut &'s str) -> ModalResult<Tag<'s>> {
-    dbg!(&input);
+fn tag<'s>(source: &'s str, input: &mut &'s str) -> ModalResult<Tag<'s>> {
+    let start = input.offset_from(&source);
     let tag_type = alt((closing_tag, opening_tag)).parse_next(input)?;
 
-    let attributes = dispatch!(peek(any);
+    let pairs = dispatch!(peek(any);
         '>' => tag_close,
         ' ' => tag_open,
         '/' => self_closing_tag,
     _ => fail
     )
-    .parse_next(input)?
-    .into_attributes();
-    let res = if let Some(children) = opt(parse).parse_next(input)? {
-        Tag {
-            tag_type,
-            attributes,
-            content: content(input).ok(),
-            children,
+    .parse_next(input)?;
+    let mut attributes = Vec::with_capacity(pairs.len());
+    let mut attribute_spans = Vec::with_capacity(pairs.len());
+    for (k, v) in pairs {
+        let attribute = Attribute::try_from((k, v))
+            .map_err(|error| invalid(input, format!("`{k}` attribute: {error}")))?;
+        let mut end = v.offset_from(&source) + v.len();
+        if source[end..].starts_with('"') {
+            end += 1;
         }
-    } else {
-        Tag {
-            tag_type,
-            attributes,
-            content: content(input).ok(),
-            children: Vec::new(),
-        }
-    };
+        attributes.push(attribute);
+        attribute_spans.push(Span::new(k.offset_from(&source), end));
+    }
+    let children = opt(|input: &mut &'s str| parse_in(source, input))
+        .parse_next(input)?
+        .unwrap_or_default();
+    let content = content(input).ok();
+    let content_span =
+        content.map(|content| Span::new(content.offset_from(&source), input.offset_from(&source)));
 
     closing_tag.parse_next(input)?;
 
-    Ok(res)
+    Ok(Tag {
+        tag_type,
+        attributes: attributes.into(),
+        content: content.map(Cow::Borrowed),
+        children,
+        spans: Spans {
+            element: Span::new(start, input.offset_from(&source)),
+            content: content_span,
+            attributes: attribute_spans,
+        },
+    })
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TagType {
     Primitive(Primitive),
     Core(Core),
@@ -134,13 +267,36 @@ impl FromStr for TagType {
             "dc" | "citableAs" | "docStage" | "currentChamber" | "processedBy"
             | "processedDate" | "congress" | "session" | "relatedDocument" | "publicPrivate"
             | "img" => TagType::Meta(Meta::from_str(s)?),
+            "table" | "caption" | "colgroup" | "col" | "thead" | "tbody" | "tfoot" | "tr"
+            | "th" | "td" => TagType::Table(Table::from_str(s)?),
             _ => panic!("Unknown TagType: {:#?}", s),
         };
         Ok(tag)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl TagType {
+    /// The USLM element name this tag type was parsed from.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TagType::Primitive(t) => t.name(),
+            TagType::Core(t) => t.name(),
+            TagType::Generic(t) => t.name(),
+            TagType::Doc(t) => t.name(),
+            TagType::Property(t) => t.name(),
+            TagType::Title(t) => t.name(),
+            TagType::Level(t) => t.name(),
+            TagType::Note(t) => t.name(),
+            TagType::Signature(t) => t.name(),
+            TagType::Appendix(t) => t.name(),
+            TagType::Other(t) => t.name(),
+            TagType::Meta(t) => t.name(),
+            TagType::Table(t) => t.name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Primitive {
     Marker,
     Inline,
@@ -163,7 +319,18 @@ impl FromStr for Primitive {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Primitive {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Primitive::Marker => "marker",
+            Primitive::Inline => "inline",
+            Primitive::Block => "block",
+            Primitive::Content => "content",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Core {
     LawDoc,
     Document,
@@ -236,7 +403,43 @@ impl FromStr for Core {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Core {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Core::LawDoc => "lawDoc",
+            Core::Document => "document",
+            Core::Meta => "meta",
+            Core::Property => "property",
+            Core::Set => "set",
+            Core::Toc => "toc",
+            Core::TocItem => "tocItem",
+            Core::Main => "main",
+            Core::Statement => "statement",
+            Core::Preamble => "preamble",
+            Core::Recital => "recital",
+            Core::EnactingFormula => "enactingFormula",
+            Core::Level => "level",
+            Core::Num => "num",
+            Core::Text => "text",
+            Core::Heading => "heading",
+            Core::Subheading => "subheading",
+            Core::Crossheading => "crossheading",
+            Core::Instruction => "instruction",
+            Core::Action => "action",
+            Core::Notes => "notes",
+            Core::Note => "note",
+            Core::Appendix => "appendix",
+            Core::Signatures => "signatures",
+            Core::Signature => "signature",
+            Core::Ref => "ref",
+            Core::Date => "date",
+            Core::QuotedText => "quotedText",
+            Core::QuotedContent => "quotedContent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Generic {
     Layout,
     Header,
@@ -263,8 +466,21 @@ impl FromStr for Generic {
     }
 }
 
+impl Generic {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Generic::Layout => "layout",
+            Generic::Header => "header",
+            Generic::Row => "row",
+            Generic::Column => "column",
+            Generic::B => "b",
+            Generic::I => "i",
+        }
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Doc {
     Bill,
     Statute,
@@ -289,8 +505,20 @@ impl FromStr for Doc {
     }
 }
 
+impl Doc {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Doc::Bill => "bill",
+            Doc::Statute => "statute",
+            Doc::Resolution => "resolution",
+            Doc::Amendment => "amendment",
+            Doc::UscDoc => "uscDoc",
+        }
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Property {
     DocNumber,
     DocPublicationName,
@@ -311,8 +539,18 @@ impl FromStr for Property {
     }
 }
 
+impl Property {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Property::DocNumber => "docNumber",
+            Property::DocPublicationName => "docPublicationName",
+            Property::DocReleasePoint => "docReleasePoint",
+        }
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Title {
     DocTitle,
     LongTitle,
@@ -333,7 +571,22 @@ impl FromStr for Title {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Title {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Title::DocTitle => "docTitle",
+            Title::LongTitle => "longTitle",
+            Title::ShortTitle => "shortTitle",
+        }
+    }
+}
+
+/// Hierarchical levels, declared from the outermost `title` to the
+/// innermost `subsubitem`, followed by the special-purpose levels. Levels
+/// compare by [`Level::rank`], so an outer level compares less than the
+/// levels it contains. Levels without a rank come after all ranked ones,
+/// in the order declared here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Level {
     Preliminary,
     Title,
@@ -398,7 +651,118 @@ impl FromStr for Level {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Preliminary => "preliminary",
+            Level::Title => "title",
+            Level::Subtitle => "subtitle",
+            Level::Chapter => "chapter",
+            Level::Subchapter => "subchapter",
+            Level::Part => "part",
+            Level::Subpart => "subpart",
+            Level::Division => "division",
+            Level::Subdivision => "subdivision",
+            Level::Article => "article",
+            Level::Subarticle => "subarticle",
+            Level::Section => "section",
+            Level::Subsection => "subsection",
+            Level::Paragraph => "paragraph",
+            Level::Subparagraph => "subparagraph",
+            Level::Clause => "clause",
+            Level::Subclause => "subclause",
+            Level::Item => "item",
+            Level::Subitem => "subitem",
+            Level::Subsubitem => "subsubitem",
+            Level::CompiledAct => "compiledAct",
+            Level::CourtRules => "courtRules",
+            Level::CourtRule => "courtRule",
+            Level::ReorganizationPlans => "reorganizationPlans",
+            Level::ReorganizationPlan => "reorganizationPlan",
+        }
+    }
+
+    /// The depth of the level in the hierarchy, 0 for `title` through 18
+    /// for `subsubitem`. The special-purpose levels have no fixed place
+    /// and no rank.
+    pub fn rank(&self) -> Option<u8> {
+        HIERARCHY
+            .iter()
+            .position(|level| level == self)
+            .map(|rank| rank as u8)
+    }
+
+    /// The levels that may appear directly inside this one, following the
+    /// House drafting conventions: levels above the section hold any
+    /// smaller level above the section or sections, a section holds
+    /// subsections or paragraphs, and each level below that holds only
+    /// the next one.
+    pub fn allowed_children(&self) -> &'static [Level] {
+        const SECTION: usize = 10;
+        match (self, self.rank()) {
+            (Level::Section, _) => &[Level::Subsection, Level::Paragraph],
+            (_, Some(rank)) if (rank as usize) < SECTION => &HIERARCHY[rank as usize + 1..=SECTION],
+            (_, Some(rank)) => {
+                let rank = rank as usize;
+                &HIERARCHY[rank + 1..(rank + 2).min(HIERARCHY.len())]
+            }
+            (Level::CourtRules, None) => &[Level::CourtRule],
+            (Level::ReorganizationPlans, None) => &[Level::ReorganizationPlan],
+            (_, None) => &HIERARCHY[..=SECTION],
+        }
+    }
+
+    /// Whether this level is at the same depth as `parent` or above it,
+    /// as a `paragraph` inside a `clause`. Special-purpose levels are
+    /// never inverted.
+    pub fn is_inverted_in(&self, parent: Level) -> bool {
+        match (self.rank(), parent.rank()) {
+            (Some(child), Some(parent)) => child <= parent,
+            _ => false,
+        }
+    }
+}
+
+impl Ord for Level {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |level: &Level| match level.rank() {
+            Some(rank) => (false, rank),
+            None => (true, *level as u8),
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for Level {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The hierarchical levels from the outermost to the innermost.
+pub(crate) const HIERARCHY: [Level; 19] = [
+    Level::Title,
+    Level::Subtitle,
+    Level::Chapter,
+    Level::Subchapter,
+    Level::Part,
+    Level::Subpart,
+    Level::Division,
+    Level::Subdivision,
+    Level::Article,
+    Level::Subarticle,
+    Level::Section,
+    Level::Subsection,
+    Level::Paragraph,
+    Level::Subparagraph,
+    Level::Clause,
+    Level::Subclause,
+    Level::Item,
+    Level::Subitem,
+    Level::Subsubitem,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Other {
     Def,
     Term,
@@ -423,8 +787,20 @@ impl FromStr for Other {
     }
 }
 
+impl Other {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Other::Def => "def",
+            Other::Term => "term",
+            Other::Chapeau => "chapeau",
+            Other::Continuation => "continuation",
+            Other::Proviso => "proviso",
+        }
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Note {
     SourceCredit,
     StatutoryNote,
@@ -447,7 +823,18 @@ impl FromStr for Note {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Note {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Note::SourceCredit => "sourceCredit",
+            Note::StatutoryNote => "statutoryNote",
+            Note::EditorialNote => "editorialNote",
+            Note::ChangeNote => "changeNote",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Signature {
     Made,
     Approved,
@@ -466,7 +853,16 @@ impl FromStr for Signature {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Signature {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signature::Made => "made",
+            Signature::Approved => "approved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Appendix {
     Schedule,
 }
@@ -483,7 +879,15 @@ impl FromStr for Appendix {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl Appendix {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Appendix::Schedule => "schedule",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Meta {
     // Dublin Core Metadata Elements
     Dc(Dc),
@@ -523,8 +927,28 @@ impl FromStr for Meta {
     }
 }
 
+impl Meta {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Meta::Dc(dc) => dc.name(),
+            Meta::CitableAs => "citableAs",
+            Meta::DocStage => "docStage",
+            Meta::DocPublicationName => "docPublicationName",
+            Meta::DocReleasePoint => "docReleasePoint",
+            Meta::CurrentChamber => "currentChamber",
+            Meta::ProcessedBy => "processedBy",
+            Meta::ProcessedDate => "processedDate",
+            Meta::Congress => "congress",
+            Meta::Session => "session",
+            Meta::RelatedDocument => "relatedDocument",
+            Meta::PublicPrivate => "publicPrivate",
+            Meta::Img => "img",
+        }
+    }
+}
+
 /// Dublin Core Metadata Elements
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Dc {
     Contributor,
     Coverage,
@@ -569,12 +993,42 @@ impl FromStr for Dc {
     }
 }
 
+impl Dc {
+    /// Qualified element name, including the `dc:` prefix.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dc::Contributor => "dc:contributor",
+            Dc::Coverage => "dc:coverage",
+            Dc::Creator => "dc:creator",
+            Dc::Date => "dc:date",
+            Dc::Description => "dc:description",
+            Dc::Format => "dc:format",
+            Dc::Identifier => "dc:identifier",
+            Dc::Language => "dc:language",
+            Dc::Publisher => "dc:publisher",
+            Dc::Relation => "dc:relation",
+            Dc::Rights => "dc:rights",
+            Dc::Source => "dc:source",
+            Dc::Subject => "dc:subject",
+            Dc::Title => "dc:title",
+            Dc::Type => "dc:type",
+        }
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Table {
     Table,
-    Th,
+    Caption,
+    Colgroup,
+    Col,
+    Thead,
+    Tbody,
+    Tfoot,
     Tr,
+    Th,
+    Td,
 }
 
 impl FromStr for Table {
@@ -583,14 +1037,38 @@ impl FromStr for Table {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let item = match s {
             "table" => Table::Table,
-            "th" => Table::Th,
+            "caption" => Table::Caption,
+            "colgroup" => Table::Colgroup,
+            "col" => Table::Col,
+            "thead" => Table::Thead,
+            "tbody" => Table::Tbody,
+            "tfoot" => Table::Tfoot,
             "tr" => Table::Tr,
+            "th" => Table::Th,
+            "td" => Table::Td,
             _ => panic!("Unkown Meta: {:#?}", s,),
         };
         Ok(item)
     }
 }
 
+impl Table {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Table => "table",
+            Table::Caption => "caption",
+            Table::Colgroup => "colgroup",
+            Table::Col => "col",
+            Table::Thead => "thead",
+            Table::Tbody => "tbody",
+            Table::Tfoot => "tfoot",
+            Table::Tr => "tr",
+            Table::Th => "th",
+            Table::Td => "td",
+        }
+    }
+}
+
 /// Parses the '>' from a tag and returns the empty array
 /// required for the Tag's Vec<Attribute<'s>>.
 fn tag_close<'s>(input: &mut &'s str) -> ModalResult<Vec<(&'s str, &'s str)>> {
@@ -639,7 +1117,9 @@ fn closing_tag(input: &mut &str) -> ModalResult<TagType> {
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
+    use std::{collections::BTreeSet, vec};
+
+    use winnow::error::ErrMode;
 
     use crate::tags;
 
@@ -679,33 +1159,35 @@ mod tests {
             output,
             vec![Tag {
                 tag_type: TagType::Doc(Doc::Bill),
-                attributes: vec![],
+                attributes: vec![].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![
                  Tag {
                      tag_type: TagType::Core(Core::Meta),
-                     attributes: vec![],
+                     attributes: vec![].into(),
                      content: None,
+                     spans: Spans::default(),
                      children: vec![
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![], content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes."), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![], content: Some("Senate Bill"), children: vec![] },
-                        Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![], content: Some("2062"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S 2062 RIS"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110s2062ris"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S. 2062 RIS"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![], content: Some("Referral Instructions Senate"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![], content: Some("SENATE"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![], content: Some("United States Senate"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![], content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![], content: Some("2024-09-09"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![], content: Some("United States Government Publishing Office"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![], content: Some("text/xml"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![], content: Some("EN"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![], content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain."), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![], content: Some("110"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![], content: Some("1"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")], content: Some("[Report No. 110–238]"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![], content: Some("public"), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![].into(), content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![].into(), content: Some("Senate Bill".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![].into(), content: Some("2062".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110s2062ris".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S. 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![].into(), content: Some("Referral Instructions Senate".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![].into(), content: Some("SENATE".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![].into(), content: Some("United States Senate".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![].into(), content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![].into(), content: Some("2024-09-09".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![].into(), content: Some("United States Government Publishing Office".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![].into(), content: Some("text/xml".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![].into(), content: Some("EN".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![].into(), content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain.".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![].into(), content: Some("110".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![].into(), content: Some("1".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")].into(), content: Some("[Report No. 110–238]".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![].into(), content: Some("public".into()), spans: Spans::default(), children: vec![] },
                      ]
                  }
             ]
@@ -745,28 +1227,29 @@ mod tests {
             vec![
                  Tag {
                      tag_type: TagType::Core(Core::Meta),
-                     attributes: vec![],
+                     attributes: vec![].into(),
                      content: None,
+                     spans: Spans::default(),
                      children: vec![
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![], content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes."), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![], content: Some("Senate Bill"), children: vec![] },
-                        Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![], content: Some("2062"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S 2062 RIS"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110s2062ris"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S. 2062 RIS"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![], content: Some("Referral Instructions Senate"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![], content: Some("SENATE"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![], content: Some("United States Senate"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![], content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![], content: Some("2024-09-09"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![], content: Some("United States Government Publishing Office"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![], content: Some("text/xml"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![], content: Some("EN"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![], content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain."), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![], content: Some("110"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![], content: Some("1"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")], content: Some("[Report No. 110–238]"), children: vec![] },
-                        Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![], content: Some("public"), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![].into(), content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![].into(), content: Some("Senate Bill".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![].into(), content: Some("2062".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110s2062ris".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S. 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![].into(), content: Some("Referral Instructions Senate".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![].into(), content: Some("SENATE".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![].into(), content: Some("United States Senate".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![].into(), content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![].into(), content: Some("2024-09-09".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![].into(), content: Some("United States Government Publishing Office".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![].into(), content: Some("text/xml".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![].into(), content: Some("EN".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![].into(), content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain.".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![].into(), content: Some("110".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![].into(), content: Some("1".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")].into(), content: Some("[Report No. 110–238]".into()), spans: Spans::default(), children: vec![] },
+                        Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![].into(), content: Some("public".into()), spans: Spans::default(), children: vec![] },
                      ]
                  }
             ]
@@ -801,25 +1284,25 @@ mod tests {
         assert_eq!(
             output,
              vec![
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![], content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes."), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![], content: Some("Senate Bill"), children: vec![] },
-                Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![], content: Some("2062"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S 2062 RIS"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110s2062ris"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S. 2062 RIS"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![], content: Some("Referral Instructions Senate"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![], content: Some("SENATE"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![], content: Some("United States Senate"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![], content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![], content: Some("2024-09-09"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![], content: Some("United States Government Publishing Office"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![], content: Some("text/xml"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![], content: Some("EN"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![], content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain."), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![], content: Some("110"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![], content: Some("1"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")], content: Some("[Report No. 110–238]"), children: vec![] },
-                Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![], content: Some("public"), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![].into(), content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![].into(), content: Some("Senate Bill".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![].into(), content: Some("2062".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110s2062ris".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S. 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![].into(), content: Some("Referral Instructions Senate".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![].into(), content: Some("SENATE".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![].into(), content: Some("United States Senate".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![].into(), content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![].into(), content: Some("2024-09-09".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![].into(), content: Some("United States Government Publishing Office".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![].into(), content: Some("text/xml".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![].into(), content: Some("EN".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![].into(), content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain.".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![].into(), content: Some("110".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![].into(), content: Some("1".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")].into(), content: Some("[Report No. 110–238]".into()), spans: Spans::default(), children: vec![] },
+                Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![].into(), content: Some("public".into()), spans: Spans::default(), children: vec![] },
             ]
         )
     }
@@ -839,8 +1322,10 @@ mod tests {
                     Attribute::Role("report"),
                     Attribute::Href("/us/srpt/110/238"),
                     Attribute::Value("CRPT-110srpt238")
-                ],
-                content: Some("[Report No. 110–238]"),
+                ]
+                .into(),
+                content: Some("[Report No. 110–238]".into()),
+                spans: Spans::default(),
                 children: vec![]
             },]
         )
@@ -850,15 +1335,16 @@ mod tests {
     fn test_parse_tag() {
         let mut input = "<property name=&quot;docTitle&quot;>CONTENT</property>";
 
-        let output = tag(&mut input).unwrap();
+        let output = tag(input, &mut input).unwrap();
 
         assert_eq!(input, "");
         assert_eq!(
             output,
             Tag {
                 tag_type: TagType::Core(Core::Property),
-                attributes: vec![Attribute::Name("&quot;docTitle&quot;")],
-                content: Some("CONTENT"),
+                attributes: vec![Attribute::Name("&quot;docTitle&quot;")].into(),
+                content: Some("CONTENT".into()),
+                spans: Spans::default(),
                 children: vec![],
             }
         )
@@ -868,15 +1354,16 @@ mod tests {
     fn test_parse_meta_tag() {
         let mut input = "<meta></meta>";
 
-        let output = tag(&mut input).unwrap();
+        let output = tag(input, &mut input).unwrap();
 
         assert_eq!(input, "");
         assert_eq!(
             output,
             Tag {
                 tag_type: TagType::Core(Core::Meta),
-                attributes: vec![],
+                attributes: vec![].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![],
             }
         )
@@ -886,20 +1373,49 @@ mod tests {
     fn test_parse_meta_tag_content() {
         let mut input = "<meta>CONTENT</meta>";
 
-        let output = tag(&mut input).unwrap();
+        let output = tag(input, &mut input).unwrap();
 
         assert_eq!(input, "");
         assert_eq!(
             output,
             Tag {
                 tag_type: TagType::Core(Core::Meta),
-                attributes: vec![],
-                content: Some("CONTENT"),
+                attributes: vec![].into(),
+                content: Some("CONTENT".into()),
+                spans: Spans::default(),
                 children: vec![],
             }
         )
     }
 
+    #[test]
+    fn test_parse_tag_spans() {
+        let mut input = "<meta> <congress>110</congress></meta>";
+
+        let output = parse(&mut input).unwrap();
+
+        assert_eq!(input, "");
+        assert_eq!(output[0].span(), Span::new(0, 38));
+        assert_eq!(output[0].children[0].span(), Span::new(7, 31));
+    }
+
+    #[test]
+    fn test_parse_attribute_and_content_spans() {
+        let mut input = r#"<relatedDocument role="report" href=/us/srpt/110/238>[Report No. 110–238]</relatedDocument>"#;
+
+        let output = parse(&mut input).unwrap();
+
+        assert_eq!(input, "");
+        assert_eq!(
+            output[0].spans,
+            Spans {
+                element: Span::new(0, 93),
+                content: Some(Span::new(53, 75)),
+                attributes: vec![Span::new(17, 30), Span::new(31, 52)],
+            }
+        );
+    }
+
     #[test]
     fn test_parse_self_closing() {
         let mut input = "/>";
@@ -944,15 +1460,16 @@ mod tests {
     fn test_dc_tag() {
         let mut input = "<dc:title></dc:title>";
 
-        let output = tag(&mut input).unwrap();
+        let output = tag(input, &mut input).unwrap();
 
         assert_eq!(input, "");
         assert_eq!(
             output,
             Tag {
                 tag_type: TagType::Meta(Meta::Dc(Dc::Title)),
-                attributes: vec![],
+                attributes: vec![].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![]
             }
         );
@@ -969,8 +1486,9 @@ mod tests {
             output,
             vec![Tag {
                 tag_type: TagType::Meta(Meta::Dc(Dc::Title)),
-                attributes: vec![],
-                content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes."),
+                attributes: vec![].into(),
+                content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.".into()),
+                spans: Spans::default(),
                 children: vec![]
             }]
         );
@@ -980,15 +1498,16 @@ mod tests {
     fn test_dc_tag_content() {
         let mut input = "<dc:title>CONTENT</dc:title>";
 
-        let output = tag(&mut input).unwrap();
+        let output = tag(input, &mut input).unwrap();
 
         assert_eq!(input, "");
         assert_eq!(
             output,
             Tag {
                 tag_type: TagType::Meta(Meta::Dc(Dc::Title)),
-                attributes: vec![],
-                content: Some("CONTENT"),
+                attributes: vec![].into(),
+                content: Some("CONTENT".into()),
+                spans: Spans::default(),
                 children: vec![]
             }
         );
@@ -1040,20 +1559,23 @@ mod tests {
             vec![
                 Tag {
                     tag_type: TagType::Generic(tags::Generic::Column),
-                    attributes: vec![],
-                    content: Some("1."),
+                    attributes: vec![].into(),
+                    content: Some("1.".into()),
+                    spans: Spans::default(),
                     children: vec![]
                 },
                 Tag {
                     tag_type: TagType::Generic(tags::Generic::Column),
-                    attributes: vec![Attribute::Leaders(".")],
-                    content: Some("General Provisions"),
+                    attributes: vec![Attribute::Leaders(".")].into(),
+                    content: Some("General Provisions".into()),
+                    spans: Spans::default(),
                     children: vec![]
                 },
                 Tag {
                     tag_type: TagType::Generic(tags::Generic::Column),
-                    attributes: vec![],
-                    content: Some("101"),
+                    attributes: vec![].into(),
+                    content: Some("101".into()),
+                    spans: Spans::default(),
                     children: vec![]
                 },
             ]
@@ -1071,8 +1593,9 @@ mod tests {
             output,
             vec![Tag {
                 tag_type: TagType::Core(Core::TocItem),
-                attributes: vec![],
+                attributes: vec![].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![],
             },]
         );
@@ -1089,8 +1612,9 @@ mod tests {
             output,
             vec![Tag {
                 tag_type: TagType::Core(Core::TocItem),
-                attributes: vec![Attribute::Title("Chapter 1")],
+                attributes: vec![Attribute::Title("Chapter 1")].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![],
             },]
         );
@@ -1108,25 +1632,29 @@ mod tests {
             output,
             vec![Tag {
                 tag_type: TagType::Core(Core::TocItem),
-                attributes: vec![Attribute::Title("Chapter 1")],
+                attributes: vec![Attribute::Title("Chapter 1")].into(),
                 content: None,
+                spans: Spans::default(),
                 children: vec![
                     Tag {
                         tag_type: TagType::Generic(tags::Generic::Column),
-                        attributes: vec![],
-                        content: Some("1."),
+                        attributes: vec![].into(),
+                        content: Some("1.".into()),
+                        spans: Spans::default(),
                         children: vec![]
                     },
                     Tag {
                         tag_type: TagType::Generic(tags::Generic::Column),
-                        attributes: vec![Attribute::Leaders(".")],
-                        content: Some("General Provisions"),
+                        attributes: vec![Attribute::Leaders(".")].into(),
+                        content: Some("General Provisions".into()),
+                        spans: Spans::default(),
                         children: vec![]
                     },
                     Tag {
                         tag_type: TagType::Generic(tags::Generic::Column),
-                        attributes: vec![],
-                        content: Some("101"),
+                        attributes: vec![].into(),
+                        content: Some("101".into()),
+                        spans: Spans::default(),
                         children: vec![]
                     },
                 ]
@@ -1136,10 +1664,127 @@ mod tests {
 
     #[test]
     fn test_law_doc() {
-        let mut input = r#"<lawDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" xsi:schemaLocation="http://xml.house.gov/schemas/uslm/1.0" xml:base="http://resolver.mydomain.com" identifier="/us/usc/t5">
+        let mut input = r#"<lawDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" xsi:schemaLocation="http://xml.house.gov/schemas/uslm/1.0 http://xml.house.gov/schemas/uslm/1.0/USLM-1.0.xsd" xml:base="http://resolver.mydomain.com" identifier="/us/usc/t5">
 </lawDoc>"#;
 
         let _output = parse(&mut input).unwrap();
         assert_eq!(input, "");
     }
+
+    #[test]
+    fn test_level_rank_and_children() {
+        assert_eq!(Level::Title.rank(), Some(0));
+        assert_eq!(Level::Subsubitem.rank(), Some(18));
+        assert_eq!(Level::CourtRule.rank(), None);
+        assert!(Level::Chapter < Level::Section);
+        assert!(Level::Title < Level::Subtitle);
+        assert!(Level::Subsubitem < Level::Preliminary);
+        assert!(Level::Preliminary < Level::CompiledAct);
+        assert!(Level::CourtRule <= Level::CourtRule);
+        assert_eq!(
+            Level::Article.allowed_children(),
+            &[Level::Subarticle, Level::Section]
+        );
+        assert_eq!(
+            Level::Section.allowed_children(),
+            &[Level::Subsection, Level::Paragraph]
+        );
+        assert_eq!(Level::Clause.allowed_children(), &[Level::Subclause]);
+        assert_eq!(Level::Subsubitem.allowed_children(), &[]);
+        assert_eq!(Level::CourtRules.allowed_children(), &[Level::CourtRule]);
+        assert!(Level::Paragraph.is_inverted_in(Level::Clause));
+        assert!(Level::Clause.is_inverted_in(Level::Clause));
+        assert!(!Level::Subparagraph.is_inverted_in(Level::Subsection));
+    }
+
+    #[test]
+    fn test_level_sort() {
+        let mut levels = vec![
+            Level::CourtRule,
+            Level::Section,
+            Level::Preliminary,
+            Level::Title,
+            Level::Subsubitem,
+            Level::CompiledAct,
+            Level::Chapter,
+        ];
+
+        levels.sort();
+
+        assert_eq!(
+            levels,
+            vec![
+                Level::Title,
+                Level::Chapter,
+                Level::Section,
+                Level::Subsubitem,
+                Level::Preliminary,
+                Level::CompiledAct,
+                Level::CourtRule,
+            ]
+        );
+        assert_eq!(
+            levels
+                .iter()
+                .rev()
+                .copied()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            levels
+        );
+    }
+
+    #[test]
+    fn test_tag_attribute_helpers() {
+        let mut input = r#"<section id="s552" identifier="/us/usc/t5/s552" role="definitions"><table><tr><td colspan="2"></td></tr></table></section>"#;
+        let mut section = parse(&mut input).unwrap().remove(0);
+
+        assert_eq!(section.id(), Some("s552"));
+        assert_eq!(section.identifier(), Some("/us/usc/t5/s552"));
+        assert_eq!(section.href(), None);
+        assert_eq!(section.role(), Some("definitions"));
+        let cell = &section.children[0].children[0].children[0];
+        assert_eq!(
+            cell.attr::<kind::ColSpan>().map(|span| *span.value()),
+            Some(2)
+        );
+
+        section.set_identifier("/us/usc/t5/s553");
+        section.set_href("/us/usc/t5/s552");
+        assert_eq!(
+            section.remove_attr::<kind::Id>(),
+            Some(Attribute::Id("s552"))
+        );
+
+        assert_eq!(section.id(), None);
+        assert_eq!(section.identifier(), Some("/us/usc/t5/s553"));
+        assert_eq!(
+            section.attributes,
+            vec![
+                Attribute::Identifier("/us/usc/t5/s553"),
+                Attribute::Role("definitions"),
+                Attribute::Href("/us/usc/t5/s552"),
+            ]
+        );
+        assert_eq!(
+            section.spans.attributes,
+            vec![Span::new(19, 47), Span::new(48, 66), Span::default()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_attribute() {
+        let mut input = r#"<table><tr><td colspan="two"></td></tr></table>"#;
+
+        let output = parse(&mut input).unwrap_err();
+
+        let ErrMode::Cut(output) = output else {
+            panic!("expected a cut error, got {output:?}");
+        };
+        assert_eq!(
+            output.to_string(),
+            "`colspan` attribute: invalid value `two`: invalid digit found in string"
+        );
+    }
 }
@@ -0,0 +1,287 @@
+use std::{fmt, str::FromStr};
+
+use winnow::{
+    token::{literal, take_while},
+    ModalResult, Parser,
+};
+
+use crate::{
+    attributes::{kind, Attribute},
+    tags::{Tag, TagType},
+    Uslm,
+};
+
+/// A calendar date as written in USLM date attributes, `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// The date, or `None` when `month` or `day` is out of range, as
+    /// `2019-02-29`.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        (1..=Date::days_in_month(year, month))
+            .contains(&day)
+            .then_some(Date { year, month, day })
+    }
+
+    /// The number of days in `month` of `year`, 0 for a month out of
+    /// range.
+    fn days_in_month(year: u16, month: u8) -> u8 {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses the date at the start of `s`, ignoring any time that follows.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut input = s;
+        date(&mut input).map_err(|_| format!("`{s}` is not a YYYY-MM-DD date"))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub(crate) fn date(input: &mut &str) -> ModalResult<Date> {
+    let digits = |n| take_while(n, |c: char| c.is_ascii_digit());
+    (
+        digits(4).parse_to(),
+        literal("-"),
+        digits(2).parse_to(),
+        literal("-"),
+        digits(2).parse_to(),
+    )
+        .verify_map(|(year, _, month, _, day): (u16, _, u8, _, u8)| Date::new(year, month, day))
+        .parse_next(input)
+}
+
+/// One temporal version of a provision, see [`Uslm::versions`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct TemporalVersion<'a, 's> {
+    pub temporal_id: Option<&'s str>,
+    /// The first day the version is in force, when known.
+    pub begin: Option<Date>,
+    /// The last day the version is in force, when known.
+    pub end: Option<Date>,
+    pub status: Option<&'s str>,
+    pub tag: &'a Tag<'s>,
+}
+
+impl<'s> Uslm<'s> {
+    /// A copy of the document holding only the provisions in force on
+    /// `date`. A provision is in force from its `beginDate`, `startPeriod`
+    /// or `commencementDate` through its `endDate` or `endPeriod`, and
+    /// provisions without dates follow the provision containing them. A
+    /// `status` such as `repealed` or `proposed` takes the provision out
+    /// for the whole period its dates give.
+    pub fn as_of(&self, date: Date) -> Uslm<'s> {
+        Uslm {
+            header: self.header.clone(),
+            content: in_force(&self.content, date),
+        }
+    }
+
+    /// Every version of the provision whose `identifier` or `temporalId`
+    /// is `id`, ordered by the date each came into force.
+    pub fn versions(&self, id: &str) -> Vec<TemporalVersion<'_, 's>> {
+        let mut versions = Vec::new();
+        let mut stack: Vec<&Tag<'s>> = self.content.iter().rev().collect();
+        while let Some(tag) = stack.pop() {
+            let matches = tag.attributes.iter().any(|attribute| {
+                matches!(attribute, Attribute::Identifier(value) | Attribute::TemporalId(value) if *value == id)
+            });
+            if matches && !matches!(tag.tag_type, TagType::Doc(_)) {
+                let (begin, end) = period(tag);
+                versions.push(TemporalVersion {
+                    temporal_id: tag.attributes.iter().find_map(|attribute| match attribute {
+                        Attribute::TemporalId(value) => Some(*value),
+                        _ => None,
+                    }),
+                    begin,
+                    end,
+                    status: tag.attributes.iter().find_map(|attribute| match attribute {
+                        Attribute::Status(value) => Some(*value),
+                        _ => None,
+                    }),
+                    tag,
+                });
+            }
+            stack.extend(tag.children.iter().rev());
+        }
+        versions.sort_by_key(|version| version.begin);
+        versions
+    }
+}
+
+fn in_force<'s>(tags: &[Tag<'s>], date: Date) -> Vec<Tag<'s>> {
+    tags.iter()
+        .filter(|tag| {
+            let (begin, end) = period(tag);
+            let status = tag.attr::<kind::Status>();
+            begin.is_none_or(|begin| begin <= date)
+                && end.is_none_or(|end| date <= end)
+                && !status.is_some_and(|status| NOT_IN_FORCE.contains(status))
+        })
+        .map(|tag| Tag {
+            children: in_force(&tag.children, date),
+            ..tag.clone()
+        })
+        .collect()
+}
+
+/// The `status` values of provisions that are not in force, whether never
+/// or no longer.
+const NOT_IN_FORCE: &[&str] = &[
+    "proposed",
+    "pending",
+    "withdrawn",
+    "cancelled",
+    "notAdopted",
+    "suspended",
+    "repealed",
+    "expired",
+    "terminated",
+    "omitted",
+    "transferred",
+];
+
+/// The first and last days a tag is in force, from its temporal
+/// attributes. Unparseable dates are treated as missing.
+fn period(tag: &Tag) -> (Option<Date>, Option<Date>) {
+    let mut begin = None;
+    let mut end = None;
+    for attribute in &tag.attributes {
+        match attribute {
+            Attribute::BeginDate(date) | Attribute::CommencementDate(date) => {
+                begin = begin.or(Some(*date.value()));
+            }
+            Attribute::StartPeriod(value) => {
+                begin = begin.or_else(|| value.parse().ok());
+            }
+            Attribute::EndDate(date) => {
+                end = end.or(Some(*date.value()));
+            }
+            Attribute::EndPeriod(value) => {
+                end = end.or_else(|| value.parse().ok());
+            }
+            _ => {}
+        }
+    }
+    (begin, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<section identifier="/us/usc/t5/s552">
+<num value="552">Sec. 552.</num>
+<subsection identifier="/us/usc/t5/s552/a" temporalId="s552_a_v1" startPeriod="1967-07-04" endPeriod="2016-06-29" status="operational">
+<num value="a">a.</num>
+<content>Each agency shall make available to the public information</content>
+</subsection>
+<subsection identifier="/us/usc/t5/s552/a" temporalId="s552_a_v2" startPeriod="2016-06-30">
+<num value="a">a.</num>
+<content>Each agency shall make available to the public and in electronic format information</content>
+</subsection>
+<subsection identifier="/us/usc/t5/s552/g" beginDate="2020-01-01">
+<num value="g">g.</num>
+<content>The head of each agency shall prepare guides</content>
+</subsection>
+<subsection identifier="/us/usc/t5/s552/h" status="repealed">
+<num value="h">h.</num>
+<content>Repealed</content>
+</subsection>
+</section>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!("2019-01-01".parse().ok(), Date::new(2019, 1, 1));
+        assert_eq!("2019-01-01T12:00:00".parse().ok(), Date::new(2019, 1, 1));
+        assert!("2019-13-01".parse::<Date>().is_err());
+        assert!("2019-02-31".parse::<Date>().is_err());
+        assert!("2019-04-31".parse::<Date>().is_err());
+        assert!("2019-02-29".parse::<Date>().is_err());
+        assert!("1900-02-29".parse::<Date>().is_err());
+        assert_eq!("2000-02-29".parse().ok(), Date::new(2000, 2, 29));
+        assert_eq!("2020-02-29".parse().ok(), Date::new(2020, 2, 29));
+        assert!("01/01/2019".parse::<Date>().is_err());
+        assert_eq!(Date::new(2019, 1, 1).unwrap().to_string(), "2019-01-01");
+        assert_eq!(Date::new(2019, 13, 45), None);
+        assert_eq!(Date::new(2019, 2, 29), None);
+        assert_eq!(Date::new(2019, 4, 0), None);
+    }
+
+    #[test]
+    fn test_as_of() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.as_of(Date::new(2019, 1, 1).unwrap());
+
+        let section = &output.content[0].children[0].children[0];
+        let texts: Vec<String> = section.children.iter().map(Tag::text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Sec. 552.",
+                "a. Each agency shall make available to the public and in electronic format information",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output: Vec<_> = uslm
+            .versions("/us/usc/t5/s552/a")
+            .into_iter()
+            .map(|version| {
+                (
+                    version.temporal_id,
+                    version.begin,
+                    version.end,
+                    version.status,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            output,
+            vec![
+                (
+                    Some("s552_a_v1"),
+                    Date::new(1967, 7, 4),
+                    Date::new(2016, 6, 29),
+                    Some("operational")
+                ),
+                (Some("s552_a_v2"), Date::new(2016, 6, 30), None, None),
+            ]
+        );
+    }
+}
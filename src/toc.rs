@@ -0,0 +1,324 @@
+use std::borrow::Cow;
+
+use crate::{
+    attributes::Attribute,
+    designation::Designation,
+    span::{Span, Spans},
+    tags::{Core, Generic, Tag, TagType},
+    validate::Diagnostic,
+    Uslm,
+};
+
+impl<'s> Uslm<'s> {
+    /// A `toc` listing the levels of the document nested at most `depth`
+    /// deep, 1 giving the outermost levels only. Each `tocItem` holds a
+    /// `column` for the `num` and one for the `heading` of its level, and
+    /// an `href` to the level's `identifier` when it has one.
+    pub fn generate_toc(&self, depth: usize) -> Tag<'s> {
+        let mut entries = Vec::new();
+        for tag in &self.content {
+            collect(tag, 0, &mut entries);
+        }
+        let children = entries
+            .into_iter()
+            .filter(|entry| entry.depth <= depth)
+            .map(|entry| {
+                let attributes = entry.identifier.map(Attribute::Href).into_iter().collect();
+                let columns = vec![
+                    column(vec![], entry.num),
+                    column(vec![Attribute::Leaders(".")], entry.heading),
+                ];
+                tag(TagType::Core(Core::TocItem), attributes, columns)
+            })
+            .collect();
+        tag(TagType::Core(Core::Toc), vec![], children)
+    }
+
+    /// Compares each `toc` of the document against the levels of its body
+    /// and reports entries without a matching level, entries whose `num`
+    /// or heading differ from the level's, and levels missing from the
+    /// `toc` down to the deepest level it lists.
+    pub fn check_toc(&self) -> Vec<Diagnostic> {
+        let mut levels = Vec::new();
+        for tag in &self.content {
+            collect(tag, 0, &mut levels);
+        }
+        let mut tocs = Vec::new();
+        find_tocs(&self.content, &mut tocs);
+
+        let mut diagnostics = Vec::new();
+        for toc in tocs {
+            let mut matched = vec![false; levels.len()];
+            for item in toc
+                .children
+                .iter()
+                .filter(|child| child.tag_type == TagType::Core(Core::TocItem))
+            {
+                let entry = item_entry(item);
+                match find_level(&entry, &levels, &matched) {
+                    Some((index, problem)) => {
+                        matched[index] = true;
+                        if let Some(problem) = problem {
+                            diagnostics.push(Diagnostic::warning("toc", problem, item.span()));
+                        }
+                    }
+                    None => diagnostics.push(Diagnostic::warning(
+                        "toc",
+                        format!("`{}` matches no level of the document", entry.label()),
+                        item.span(),
+                    )),
+                }
+            }
+
+            let Some(depth) = levels
+                .iter()
+                .zip(&matched)
+                .filter(|(_, matched)| **matched)
+                .map(|(level, _)| level.depth)
+                .max()
+            else {
+                continue;
+            };
+            for (level, _) in levels
+                .iter()
+                .zip(&matched)
+                .filter(|(level, matched)| !**matched && level.depth <= depth)
+            {
+                diagnostics.push(Diagnostic::warning(
+                    "toc",
+                    format!("`{}` is missing from the table of contents", level.label()),
+                    level.span,
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// A level of the body, or an item of a `toc`.
+struct Entry<'s> {
+    identifier: Option<&'s str>,
+    num: String,
+    heading: String,
+    /// The number of levels enclosing the level, itself included.
+    depth: usize,
+    span: Span,
+}
+
+impl Entry<'_> {
+    fn label(&self) -> String {
+        format!("{} {}", self.num, self.heading).trim().to_string()
+    }
+
+    fn designation(&self) -> Option<Designation> {
+        self.num.parse().ok()
+    }
+
+    fn same_heading(&self, other: &Entry) -> bool {
+        let normalize = |heading: &str| heading.trim_end_matches('.').to_lowercase();
+        normalize(&self.heading) == normalize(&other.heading)
+    }
+}
+
+fn collect<'s>(tag: &Tag<'s>, depth: usize, entries: &mut Vec<Entry<'s>>) {
+    match tag.tag_type {
+        // Quoted content belongs to another document, and a table of
+        // contents is no part of the body.
+        TagType::Core(Core::QuotedContent | Core::Toc) => return,
+        TagType::Level(_) | TagType::Core(Core::Level) => {
+            let text = |tag_type: TagType| {
+                tag.children
+                    .iter()
+                    .find(|child| child.tag_type == tag_type)
+                    .map(Tag::text)
+                    .unwrap_or_default()
+            };
+            let entry = Entry {
+                identifier: tag.identifier(),
+                num: text(TagType::Core(Core::Num)),
+                heading: text(TagType::Core(Core::Heading)),
+                depth: depth + 1,
+                span: tag.span(),
+            };
+            if !entry.num.is_empty() || !entry.heading.is_empty() {
+                entries.push(entry);
+            }
+            for child in &tag.children {
+                collect(child, depth + 1, entries);
+            }
+            return;
+        }
+        _ => {}
+    }
+    for child in &tag.children {
+        collect(child, depth, entries);
+    }
+}
+
+fn find_tocs<'a, 's>(tags: &'a [Tag<'s>], tocs: &mut Vec<&'a Tag<'s>>) {
+    for tag in tags {
+        match tag.tag_type {
+            TagType::Core(Core::Toc) => tocs.push(tag),
+            TagType::Core(Core::QuotedContent) => {}
+            _ => find_tocs(&tag.children, tocs),
+        }
+    }
+}
+
+/// The first column of an item is its `num` and the second its heading.
+fn item_entry<'s>(item: &Tag<'s>) -> Entry<'s> {
+    let mut columns = item
+        .children
+        .iter()
+        .filter(|child| child.tag_type == TagType::Generic(Generic::Column))
+        .map(Tag::text);
+    Entry {
+        identifier: item.href(),
+        num: columns.next().unwrap_or_default(),
+        heading: columns.next().unwrap_or_default(),
+        depth: 0,
+        span: item.span(),
+    }
+}
+
+/// The index of the level an item stands for, and what differs between
+/// them. Items with an `href` match the level with that `identifier`,
+/// other items the first unmatched level with the same designation and
+/// heading, or else with the same heading or the same designation.
+fn find_level(item: &Entry, levels: &[Entry], matched: &[bool]) -> Option<(usize, Option<String>)> {
+    let designation = item.designation();
+    let same_designation =
+        |level: &Entry| designation.is_some() && level.designation() == designation;
+    let unmatched = |predicate: &dyn Fn(&Entry) -> bool| {
+        levels
+            .iter()
+            .enumerate()
+            .find(|(index, level)| !matched[*index] && predicate(level))
+    };
+
+    let (index, level) = match item.identifier {
+        Some(href) => unmatched(&|level| level.identifier == Some(href))?,
+        None => unmatched(&|level| same_designation(level) && level.same_heading(item))
+            .or_else(|| unmatched(&|level| level.same_heading(item)))
+            .or_else(|| unmatched(&same_designation))?,
+    };
+    let problem = if !same_designation(level) && item.designation().is_some() {
+        Some(format!(
+            "`{}` is numbered `{}` in the body",
+            item.label(),
+            level.num
+        ))
+    } else if !level.same_heading(item) {
+        Some(format!(
+            "`{}` is headed `{}` in the body",
+            item.label(),
+            level.heading
+        ))
+    } else {
+        None
+    };
+    Some((index, problem))
+}
+
+fn tag<'s>(tag_type: TagType, attributes: Vec<Attribute<'s>>, children: Vec<Tag<'s>>) -> Tag<'s> {
+    Tag {
+        tag_type,
+        attributes: attributes.into(),
+        content: None,
+        children,
+        spans: Spans::default(),
+    }
+}
+
+fn column<'s>(attributes: Vec<Attribute<'s>>, text: String) -> Tag<'s> {
+    Tag {
+        content: Some(Cow::Owned(text)),
+        ..tag(TagType::Generic(Generic::Column), attributes, vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INPUT: &str = r#"<uscDoc identifier="/us/usc/t5">
+<main>
+<toc>
+<tocItem><column>CHAPTER 1.</column><column leaders=".">General Provisions</column></tocItem>
+<tocItem><column>CHAPTER 2.</column><column leaders=".">Administrative Procedure</column></tocItem>
+<tocItem><column>CHAPTER 4.</column><column leaders=".">Repealed</column></tocItem>
+</toc>
+<chapter identifier="/us/usc/t5/ch1">
+<num value="1">CHAPTER 1.</num><heading>GENERAL PROVISIONS</heading>
+<section identifier="/us/usc/t5/s101"><num value="101">Sec. 101.</num><heading>Executive departments</heading></section>
+</chapter>
+<chapter identifier="/us/usc/t5/ch2"><num value="2">CHAPTER 2.</num><heading>Administrative Procedures</heading></chapter>
+<chapter identifier="/us/usc/t5/ch3"><num value="3">CHAPTER 3.</num><heading>Powers</heading></chapter>
+</main>
+</uscDoc>"#;
+
+    #[test]
+    fn test_generate_toc() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.generate_toc(1);
+
+        assert_eq!(output.tag_type, TagType::Core(Core::Toc));
+        assert_eq!(
+            output
+                .children
+                .iter()
+                .map(|item| (item.attributes.to_vec(), item.text()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    vec![Attribute::Href("/us/usc/t5/ch1")],
+                    "CHAPTER 1. GENERAL PROVISIONS".to_string()
+                ),
+                (
+                    vec![Attribute::Href("/us/usc/t5/ch2")],
+                    "CHAPTER 2. Administrative Procedures".to_string()
+                ),
+                (
+                    vec![Attribute::Href("/us/usc/t5/ch3")],
+                    "CHAPTER 3. Powers".to_string()
+                ),
+            ]
+        );
+        assert_eq!(uslm.generate_toc(2).children.len(), 4);
+    }
+
+    #[test]
+    fn test_check_toc() {
+        let mut input = INPUT;
+        let uslm = Uslm::parse(&mut input).unwrap();
+
+        let output = uslm.check_toc();
+
+        assert_eq!(
+            output
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "`CHAPTER 2. Administrative Procedure` is headed `Administrative Procedures` in the body",
+                "`CHAPTER 4. Repealed` matches no level of the document",
+                "`CHAPTER 3. Powers` is missing from the table of contents",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_generated_toc() {
+        let mut input = INPUT;
+        let mut uslm = Uslm::parse(&mut input).unwrap();
+
+        let toc = uslm.generate_toc(2);
+        uslm.content[0].children[0].children[0] = toc;
+
+        assert_eq!(uslm.check_toc(), vec![]);
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     tags::{self, Tag},
 };
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Uslm<'s> {
     pub header: Header<'s>,
     pub content: Vec<Tag<'s>>,
@@ -14,8 +14,9 @@ pub struct Uslm<'s> {
 
 impl<'s> Uslm<'s> {
     pub fn parse(input: &mut &'s str) -> ModalResult<Self> {
+        let source = *input;
         let header = Header::parse(input)?;
-        let content = tags::parse(input).unwrap_or_default();
+        let content = tags::parse_in(source, input)?;
 
         Ok(Uslm { header, content })
     }
@@ -30,8 +31,9 @@ mod tests {
     use url::Url;
 
     use crate::{
-        attributes::{Attribute, Encoding, Version},
-        header::{HeaderTag, HeaderTagType},
+        attributes::{Attribute, Encoding, Typed, Version},
+        header::{HeaderTag, HeaderTagType, XmlDeclaration},
+        span::Spans,
         tags::{Core, Dc, Doc, Generic, Meta, Primitive, Property, TagType},
     };
 
@@ -47,13 +49,12 @@ mod tests {
             output,
             Uslm {
                 header: Header {
-                    tags: vec![HeaderTag {
-                        tag_type: HeaderTagType::Xml,
-                        attributes: vec![
-                            Attribute::Version(Version::One),
-                            Attribute::Encoding(Encoding::Utf8)
-                        ],
-                    }]
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
+                    ..Header::default()
                 },
                 content: vec![],
             }
@@ -70,22 +71,16 @@ mod tests {
             output,
             Uslm {
                 header: Header {
-                    tags: vec![
-                        HeaderTag {
-                            tag_type: HeaderTagType::Xml,
-                            attributes: vec![
-                                Attribute::Version(Version::One),
-                                Attribute::Encoding(Encoding::Utf8)
-                            ],
-                        },
-                        HeaderTag {
-                            tag_type: HeaderTagType::XmlStyleSheet,
-                            attributes: vec![
-                                Attribute::Type(TEXT_CSS),
-                                Attribute::Href("uslm.css"),
-                            ]
-                        }
-                    ]
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
+                    tags: vec![HeaderTag {
+                        tag_type: HeaderTagType::XmlStyleSheet,
+                        attributes: vec![Attribute::Type(TEXT_CSS), Attribute::Href("uslm.css"),]
+                    }],
+                    ..Header::default()
                 },
                 content: vec![],
             }
@@ -94,7 +89,7 @@ mod tests {
 
     #[test]
     fn test_example_bill_start() {
-        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?><bill xmlns="http://schemas.gpo.gov/xml/uslm" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:html="http://www.w3.org/1999/xhtml" xmlns:uslm="http://schemas.gpo.gov/xml/uslm" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://schemas.gpo.gov/xml/uslm-2.1.0.xsd" xml:lang="en" id="A1"><meta>CONTENT</meta></bill>"#;
+        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?><bill xmlns="http://schemas.gpo.gov/xml/uslm" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:html="http://www.w3.org/1999/xhtml" xmlns:uslm="http://schemas.gpo.gov/xml/uslm" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://schemas.gpo.gov/xml/uslm http://schemas.gpo.gov/xml/uslm-2.1.0.xsd" xml:lang="en" id="A1"><meta>CONTENT</meta></bill>"#;
 
         let output = Uslm::parse(&mut input).unwrap();
 
@@ -102,14 +97,12 @@ mod tests {
             output,
             Uslm {
                 header: Header {
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
                     tags: vec![
-                        HeaderTag {
-                            tag_type: HeaderTagType::Xml,
-                            attributes: vec![
-                                Attribute::Version(Version::One),
-                                Attribute::Encoding(Encoding::Utf8)
-                            ],
-                        },
                         HeaderTag {
                             tag_type: HeaderTagType::XmlStyleSheet,
                             attributes: vec![
@@ -117,7 +110,8 @@ mod tests {
                                 Attribute::Href("uslm.css"),
                             ]
                         }
-                    ]
+                    ],
+                    ..Header::default()
                 },
                 content: vec![Tag {
                     tag_type: TagType::Doc(Doc::Bill),
@@ -135,17 +129,25 @@ mod tests {
                         Attribute::XmlnsiXsi(
                             Url::from_str("http://www.w3.org/2001/XMLSchema-instance").unwrap()
                         ),
-                        Attribute::XsiSchemaLocation(
-                            Url::from_str("http://schemas.gpo.gov/xml/uslm-2.1.0.xsd").unwrap()
-                        ),
+                        Attribute::XsiSchemaLocation(Typed::new(
+                            "http://schemas.gpo.gov/xml/uslm http://schemas.gpo.gov/xml/uslm-2.1.0.xsd",
+                            vec![
+                                (
+                                    Url::from_str("http://schemas.gpo.gov/xml/uslm").unwrap(),
+                                    Url::from_str("http://schemas.gpo.gov/xml/uslm-2.1.0.xsd").unwrap()
+                                )
+                            ]
+                        )),
                         Attribute::XmlLang("en"),
                         Attribute::Id("A1")
-                    ],
+                    ].into(),
                     content: None,
+                    spans: Spans::default(),
                     children: vec![Tag {
                         tag_type: TagType::Core(Core::Meta),
-                        attributes: vec![],
-                        content: Some("CONTENT"),
+                        attributes: vec![].into(),
+                        content: Some("CONTENT".into()),
+                        spans: Spans::default(),
                         children: vec![]
                     }],
                 }],
@@ -155,7 +157,7 @@ mod tests {
 
     #[test]
     fn test_example_bill_with_metadata() {
-        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?><bill xmlns="http://schemas.gpo.gov/xml/uslm" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:html="http://www.w3.org/1999/xhtml" xmlns:uslm="http://schemas.gpo.gov/xml/uslm" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://schemas.gpo.gov/xml/uslm" xml:lang="en" id="A1"><meta>
+        let mut input = r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet type="text/css" href="uslm.css"?><bill xmlns="http://schemas.gpo.gov/xml/uslm" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:html="http://www.w3.org/1999/xhtml" xmlns:uslm="http://schemas.gpo.gov/xml/uslm" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://schemas.gpo.gov/xml/uslm http://schemas.gpo.gov/xml/uslm-2.1.0.xsd" xml:lang="en" id="A1"><meta>
 <dc:title>110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.</dc:title>
 <dc:type>Senate Bill</dc:type>
 <docNumber>2062</docNumber>
@@ -183,14 +185,12 @@ mod tests {
             output,
             Uslm {
                 header: Header {
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
                     tags: vec![
-                        HeaderTag {
-                            tag_type: HeaderTagType::Xml,
-                            attributes: vec![
-                                Attribute::Version(Version::One),
-                                Attribute::Encoding(Encoding::Utf8)
-                            ],
-                        },
                         HeaderTag {
                             tag_type: HeaderTagType::XmlStyleSheet,
                             attributes: vec![
@@ -198,7 +198,8 @@ mod tests {
                                 Attribute::Href("uslm.css"),
                             ]
                         }
-                    ]
+                    ],
+                    ..Header::default()
                 },
                 content: vec![Tag {
                     tag_type: TagType::Doc(Doc::Bill),
@@ -216,37 +217,45 @@ mod tests {
                         Attribute::XmlnsiXsi(
                             Url::from_str("http://www.w3.org/2001/XMLSchema-instance").unwrap()
                         ),
-                        Attribute::XsiSchemaLocation(
-                            Url::from_str("http://schemas.gpo.gov/xml/uslm").unwrap()
-                        ),
+                        Attribute::XsiSchemaLocation(Typed::new(
+                            "http://schemas.gpo.gov/xml/uslm http://schemas.gpo.gov/xml/uslm-2.1.0.xsd",
+                            vec![
+                                (
+                                    Url::from_str("http://schemas.gpo.gov/xml/uslm").unwrap(),
+                                    Url::from_str("http://schemas.gpo.gov/xml/uslm-2.1.0.xsd").unwrap()
+                                )
+                            ]
+                        )),
                         Attribute::XmlLang("en"),
                         Attribute::Id("A1")
-                    ],
+                    ].into(),
                     content: None,
+                    spans: Spans::default(),
                     children: vec![Tag {
                         tag_type: TagType::Core(Core::Meta),
-                        attributes: vec![],
+                        attributes: vec![].into(),
                         content: None,
+                        spans: Spans::default(),
                         children: vec![
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![], content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes."), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![], content: Some("Senate Bill"), children: vec![] },
-                            Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![], content: Some("2062"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S 2062 RIS"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110s2062ris"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![], content: Some("110 S. 2062 RIS"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![], content: Some("Referral Instructions Senate"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![], content: Some("SENATE"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![], content: Some("United States Senate"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![], content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![], content: Some("2024-09-09"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![], content: Some("United States Government Publishing Office"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![], content: Some("text/xml"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![], content: Some("EN"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![], content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain."), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![], content: Some("110"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![], content: Some("1"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")], content: Some("[Report No. 110–238]"), children: vec![] },
-                            Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![], content: Some("public"), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Title)), attributes: vec![].into(), content: Some("110 S 2062 RIS: To amend the Native American Housing Assistance and Self-Determination Act of 1996 to reauthorize that Act, and for other purposes.".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Type)), attributes: vec![].into(), content: Some("Senate Bill".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Property(Property::DocNumber), attributes: vec![].into(), content: Some("2062".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110s2062ris".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::CitableAs), attributes: vec![].into(), content: Some("110 S. 2062 RIS".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::DocStage), attributes: vec![].into(), content: Some("Referral Instructions Senate".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::CurrentChamber), attributes: vec![].into(), content: Some("SENATE".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Creator)), attributes: vec![].into(), content: Some("United States Senate".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::ProcessedBy), attributes: vec![].into(), content: Some("GPO XPub Bill to USLM Generator, version 0.5 + manual changes".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::ProcessedDate), attributes: vec![].into(), content: Some("2024-09-09".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Publisher)), attributes: vec![].into(), content: Some("United States Government Publishing Office".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Format)), attributes: vec![].into(), content: Some("text/xml".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Language)), attributes: vec![].into(), content: Some("EN".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Dc(Dc::Rights)), attributes: vec![].into(), content: Some("Pursuant to Title 17 Section 105 of the United States Code, this file is not subject to copyright protection and is in the public domain.".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Congress), attributes: vec![].into(), content: Some("110".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::Session), attributes: vec![].into(), content: Some("1".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::RelatedDocument), attributes: vec![Attribute::Role("report"), Attribute::Href("/us/srpt/110/238"), Attribute::Value("CRPT-110srpt238")].into(), content: Some("[Report No. 110–238]".into()), spans: Spans::default(), children: vec![] },
+                            Tag { tag_type: TagType::Meta(Meta::PublicPrivate), attributes: vec![].into(), content: Some("public".into()), spans: Spans::default(), children: vec![] },
                         ]
                     }],
                 }],
@@ -264,13 +273,12 @@ mod tests {
             output,
             Uslm {
                 header: Header {
-                    tags: vec![HeaderTag {
-                        tag_type: HeaderTagType::Xml,
-                        attributes: vec![
-                            Attribute::Version(Version::One),
-                            Attribute::Encoding(Encoding::Utf8)
-                        ],
-                    },]
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
+                    ..Header::default()
                 },
                 content: vec![],
             }
@@ -298,46 +306,51 @@ mod tests {
             output,
             Uslm {
                 header: Header {
-                    tags: vec![HeaderTag {
-                        tag_type: HeaderTagType::Xml,
-                        attributes: vec![
-                            Attribute::Version(Version::One),
-                            Attribute::Encoding(Encoding::Utf8)
-                        ],
-                    },]
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
+                    ..Header::default()
                 },
                 content: vec![
                     Tag {
                         tag_type: TagType::Generic(tags::Generic::Header),
-                        attributes: vec![],
-                        content: Some("Table of Contents"),
+                        attributes: vec![].into(),
+                        content: Some("Table of Contents".into()),
+                        spans: Spans::default(),
                         children: vec![]
                     },
                     Tag {
                         tag_type: TagType::Core(Core::Toc),
-                        attributes: vec![],
+                        attributes: vec![].into(),
                         content: None,
+                        spans: Spans::default(),
                         children: vec![Tag {
                             tag_type: TagType::Core(Core::TocItem),
-                            attributes: vec![Attribute::Title("Chapter 1")],
+                            attributes: vec![Attribute::Title("Chapter 1")].into(),
                             content: None,
+                            spans: Spans::default(),
                             children: vec![
                                 Tag {
                                     tag_type: TagType::Generic(tags::Generic::Column),
-                                    attributes: vec![],
-                                    content: Some("1."),
+                                    attributes: vec![].into(),
+                                    content: Some("1.".into()),
+                                    spans: Spans::default(),
                                     children: vec![]
                                 },
                                 Tag {
                                     tag_type: TagType::Generic(tags::Generic::Column),
-                                    attributes: vec![Attribute::Leaders(".")],
-                                    content: Some("General Provisions"),
+                                    attributes: vec![Attribute::Leaders(".")].into(),
+                                    content: Some("General Provisions".into()),
+                                    spans: Spans::default(),
                                     children: vec![]
                                 },
                                 Tag {
                                     tag_type: TagType::Generic(tags::Generic::Column),
-                                    attributes: vec![],
-                                    content: Some("101"),
+                                    attributes: vec![].into(),
+                                    content: Some("101".into()),
+                                    spans: Spans::default(),
                                     children: vec![]
                                 },
                             ]
@@ -352,7 +365,7 @@ mod tests {
     fn test_concept_example() {
         let mut input = r#"<?xml version="1.0" encoding="UTF-8"?>
 
-<lawDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" xsi:schemaLocation="http://xml.house.gov/schemas/uslm/1.0" xml:base="http://resolver.mydomain.com" identifier="/us/usc/t5">
+<lawDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" xsi:schemaLocation="http://xml.house.gov/schemas/uslm/1.0 http://xml.house.gov/schemas/uslm/1.0/USLM-1.0.xsd" xml:base="http://resolver.mydomain.com" identifier="/us/usc/t5">
    <meta>
       <property name=&quot;docTitle&quot;></property>
    </meta>
@@ -383,13 +396,12 @@ mod tests {
             output,
             Uslm {
                 header: Header {
-                    tags: vec![HeaderTag {
-                        tag_type: HeaderTagType::Xml,
-                        attributes: vec![
-                            Attribute::Version(Version::One),
-                            Attribute::Encoding(Encoding::Utf8)
-                        ],
-                    },]
+                    declaration: Some(XmlDeclaration {
+                        version: Version::One,
+                        encoding: Some(Encoding::Utf8),
+                        standalone: None,
+                    }),
+                    ..Header::default()
                 },
                 content: vec![Tag {
                     tag_type: TagType::Core(Core::LawDoc),
@@ -397,72 +409,89 @@ mod tests {
                         Attribute::Xmlns(
                             Url::from_str("http://xml.house.gov/schemas/uslm/1.0").unwrap()
                         ),
-                        Attribute::XsiSchemaLocation(
-                            Url::from_str("http://xml.house.gov/schemas/uslm/1.0").unwrap()
-                        ),
+                        Attribute::XsiSchemaLocation(Typed::new(
+                            "http://xml.house.gov/schemas/uslm/1.0 http://xml.house.gov/schemas/uslm/1.0/USLM-1.0.xsd",
+                            vec![
+                                (
+                                    Url::from_str("http://xml.house.gov/schemas/uslm/1.0").unwrap(),
+                                    Url::from_str("http://xml.house.gov/schemas/uslm/1.0/USLM-1.0.xsd").unwrap()
+                                )
+                            ]
+                        )),
                         Attribute::XmlBase(Url::from_str("http://resolver.mydomain.com").unwrap()),
                         Attribute::Identifier("/us/usc/t5")
-                    ],
+                    ].into(),
                     content: None,
+                    spans: Spans::default(),
                     children: vec![
                         Tag {
                             tag_type: TagType::Core(Core::Meta),
-                            attributes: vec![],
+                            attributes: vec![].into(),
                             content: None,
+                            spans: Spans::default(),
                             children: vec![Tag {
                                 tag_type: TagType::Core(Core::Property),
-                                attributes: vec![Attribute::Name("&quot;docTitle&quot;")],
+                                attributes: vec![Attribute::Name("&quot;docTitle&quot;")].into(),
                                 content: None,
+                                spans: Spans::default(),
                                 children: vec![]
                             }]
                         },
                         Tag {
                             tag_type: TagType::Core(Core::Main),
-                            attributes: vec![],
+                            attributes: vec![].into(),
                             content: None,
+                            spans: Spans::default(),
                             children: vec![
                                 Tag {
                                     tag_type: TagType::Generic(Generic::Layout),
-                                    attributes: vec![],
+                                    attributes: vec![].into(),
                                     content: None,
+                                    spans: Spans::default(),
                                     children: vec![
                                         Tag {
                                             tag_type: TagType::Generic(tags::Generic::Header),
-                                            attributes: vec![],
-                                            content: Some("Table of Contents"),
+                                            attributes: vec![].into(),
+                                            content: Some("Table of Contents".into()),
+                                            spans: Spans::default(),
                                             children: vec![]
                                         },
                                         Tag {
                                             tag_type: TagType::Core(Core::Toc),
-                                            attributes: vec![],
+                                            attributes: vec![].into(),
                                             content: None,
+                                            spans: Spans::default(),
                                             children: vec![Tag {
                                                 tag_type: TagType::Core(Core::TocItem),
-                                                attributes: vec![Attribute::Title("Chapter 1")],
+                                                attributes: vec![Attribute::Title("Chapter 1")].into(),
                                                 content: None,
+                                                spans: Spans::default(),
                                                 children: vec![
                                                     Tag {
                                                         tag_type: TagType::Generic(
                                                             tags::Generic::Column
                                                         ),
-                                                        attributes: vec![],
-                                                        content: Some("1."),
+                                                        attributes: vec![].into(),
+                                                        content: Some("1.".into()),
+                                                        spans: Spans::default(),
                                                         children: vec![]
                                                     },
                                                     Tag {
                                                         tag_type: TagType::Generic(
                                                             tags::Generic::Column
                                                         ),
-                                                        attributes: vec![Attribute::Leaders(".")],
-                                                        content: Some("General Provisions"),
+                                                        attributes: vec![Attribute::Leaders(".")].into(),
+                                                        content: Some("General Provisions".into()),
+                                                        spans: Spans::default(),
                                                         children: vec![]
                                                     },
                                                     Tag {
                                                         tag_type: TagType::Generic(
                                                             tags::Generic::Column
                                                         ),
-                                                        attributes: vec![],
-                                                        content: Some("101"),
+                                                        attributes: vec![].into(),
+                                                        content: Some("101".into()),
+                                                        spans: Spans::default(),
                                                         children: vec![]
                                                     },
                                                 ]
@@ -472,25 +501,29 @@ mod tests {
                                 },
                                 Tag {
                                     tag_type: TagType::Core(Core::Level),
-                                    attributes: vec![Attribute::Role("&quot;Chapter&quot;")],
+                                    attributes: vec![Attribute::Role("&quot;Chapter&quot;")].into(),
                                     content: None,
+                                    spans: Spans::default(),
                                     children: vec![
                                         Tag {
                                             tag_type: TagType::Core(Core::Num),
-                                            attributes: vec![Attribute::Value("&quot;1&quot;")],
-                                            content: Some("CHAPTER 1."),
+                                            attributes: vec![Attribute::Value("&quot;1&quot;")].into(),
+                                            content: Some("CHAPTER 1.".into()),
+                                            spans: Spans::default(),
                                             children: vec![]
                                         },
                                         Tag {
                                             tag_type: TagType::Core(Core::Heading),
-                                            attributes: vec![],
-                                            content: Some("General Provisions"),
+                                            attributes: vec![].into(),
+                                            content: Some("General Provisions".into()),
+                                            spans: Spans::default(),
                                             children: vec![]
                                         },
                                         Tag {
                                             tag_type: TagType::Primitive(Primitive::Content),
-                                            attributes: vec![],
+                                            attributes: vec![].into(),
                                             content: None,
+                                            spans: Spans::default(),
                                             children: vec![]
                                         }
                                     ]
@@ -0,0 +1,441 @@
+use crate::{
+    attributes::Attribute,
+    span::{LineIndex, Span},
+    tags::{Appendix, Core, Level, Note, Tag, TagType},
+    Uslm,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A problem found in a document, pointing at the offending tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Short machine-readable name of the check that failed.
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            code,
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// The diagnostic as a single-line JSON object, with the `file` it was
+    /// found in and its position looked up in `index`.
+    pub fn to_json(&self, file: &str, index: &LineIndex) -> String {
+        let position = index.position(self.span.start);
+        format!(
+            r#"{{"file":{},"line":{},"column":{},"start":{},"end":{},"severity":"{}","code":{},"message":{}}}"#,
+            json_string(file),
+            position.line,
+            position.column,
+            self.span.start,
+            self.span.end,
+            self.severity.name(),
+            json_string(self.code),
+            json_string(&self.message),
+        )
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl<'s> Uslm<'s> {
+    /// Checks the document against the USLM 2.x content models for
+    /// documents, levels, notes and the core elements, and for attributes
+    /// the schema requires. An empty result means the document is valid.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for tag in &self.content {
+            if is_document(&tag.tag_type) && !has(tag, |a| matches!(a, Attribute::Xmlns(_))) {
+                diagnostics.push(Diagnostic::warning(
+                    "required-attribute",
+                    format!(
+                        "`{}` does not declare the USLM namespace",
+                        tag.tag_type.name()
+                    ),
+                    tag.span(),
+                ));
+            }
+            validate_tag(tag, None, None, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+/// `level` is the closest level containing `tag`, which need not be its
+/// parent.
+fn validate_tag(
+    tag: &Tag,
+    parent: Option<&Tag>,
+    level: Option<Level>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let name = tag.tag_type.name();
+
+    if let Some(parent) = parent {
+        if let Some(level) = level.filter(|level| inverted(&tag.tag_type, *level)) {
+            diagnostics.push(Diagnostic::error(
+                "inverted-hierarchy",
+                format!("`{name}` cannot be nested in `{}`", level.name()),
+                tag.span(),
+            ));
+        } else if !allowed_in(&tag.tag_type, &parent.tag_type) {
+            diagnostics.push(Diagnostic::error(
+                "content-model",
+                format!("`{name}` is not allowed in `{}`", parent.tag_type.name()),
+                tag.span(),
+            ));
+        }
+    } else if !is_document(&tag.tag_type) {
+        diagnostics.push(Diagnostic::error(
+            "content-model",
+            format!("`{name}` is not allowed at the top level of a document"),
+            tag.span(),
+        ));
+    }
+
+    if let Some(message) = missing_attribute(tag) {
+        diagnostics.push(Diagnostic::error("required-attribute", message, tag.span()));
+    }
+
+    check_order(tag, diagnostics);
+
+    let level = match tag.tag_type {
+        TagType::Level(level) => Some(level),
+        _ => level,
+    };
+    for child in &tag.children {
+        validate_tag(child, Some(tag), level, diagnostics);
+    }
+}
+
+fn is_document(tag_type: &TagType) -> bool {
+    matches!(
+        tag_type,
+        TagType::Doc(_) | TagType::Core(Core::LawDoc | Core::Document)
+    )
+}
+
+fn is_level(tag_type: &TagType) -> bool {
+    matches!(tag_type, TagType::Level(_) | TagType::Core(Core::Level))
+}
+
+fn is_note(tag_type: &TagType) -> bool {
+    matches!(tag_type, TagType::Note(_) | TagType::Core(Core::Note))
+}
+
+/// Whether `child` may appear directly inside `parent`. Elements not
+/// listed here are accepted anywhere.
+fn allowed_in(child: &TagType, parent: &TagType) -> bool {
+    if let TagType::Core(Core::QuotedContent) = parent {
+        // Quoted content carries fragments of other documents.
+        return true;
+    }
+    match child {
+        TagType::Core(Core::Meta) => is_document(parent),
+        TagType::Core(Core::Main) => is_document(parent),
+        TagType::Core(Core::TocItem) => parent == &TagType::Core(Core::Toc),
+        TagType::Level(child) => match parent {
+            TagType::Level(parent) => nests_in(*child, *parent),
+            TagType::Core(Core::Main | Core::Level | Core::Appendix)
+            | TagType::Appendix(Appendix::Schedule) => true,
+            _ => false,
+        },
+        TagType::Core(Core::Level) => {
+            is_level(parent)
+                || matches!(
+                    parent,
+                    TagType::Core(Core::Main | Core::Appendix) | TagType::Appendix(_)
+                )
+        }
+        TagType::Core(Core::Notes) => {
+            is_level(parent) || is_document(parent) || parent == &TagType::Core(Core::Main)
+        }
+        TagType::Note(Note::SourceCredit) => {
+            is_level(parent) || parent == &TagType::Core(Core::Notes)
+        }
+        TagType::Note(_) | TagType::Core(Core::Note) => {
+            is_level(parent) || matches!(parent, TagType::Core(Core::Notes | Core::Main))
+        }
+        TagType::Core(Core::Num | Core::Heading | Core::Subheading) => !is_document(parent),
+        _ => {
+            if parent == &TagType::Core(Core::Notes) {
+                is_note(child) || matches!(child, TagType::Core(Core::Num | Core::Heading))
+            } else {
+                true
+            }
+        }
+    }
+}
+
+/// Levels follow [`Level::allowed_children`], except that special-purpose
+/// levels such as `courtRule` may appear in any level.
+fn nests_in(child: Level, parent: Level) -> bool {
+    child.rank().is_none() || parent.allowed_children().contains(&child)
+}
+
+/// Whether a level sits at or above the depth of a level containing it,
+/// as a `paragraph` in a `clause`.
+fn inverted(child: &TagType, level: Level) -> bool {
+    match child {
+        TagType::Level(child) => child.is_inverted_in(level),
+        _ => false,
+    }
+}
+
+fn missing_attribute(tag: &Tag) -> Option<String> {
+    let missing = match tag.tag_type {
+        TagType::Core(Core::Ref)
+            if !has(tag, |a| {
+                matches!(a, Attribute::Href(_) | Attribute::Idref(_))
+            }) =>
+        {
+            "`href` or `idref`"
+        }
+        TagType::Core(Core::Property) if !has(tag, |a| matches!(a, Attribute::Name(_))) => "`name`",
+        TagType::Core(Core::Date) if !has(tag, |a| matches!(a, Attribute::Date(_))) => "`date`",
+        _ => return None,
+    };
+    Some(format!(
+        "`{}` is missing the required {missing} attribute",
+        tag.tag_type.name()
+    ))
+}
+
+fn has(tag: &Tag, predicate: impl Fn(&Attribute) -> bool) -> bool {
+    tag.attributes.iter().any(predicate)
+}
+
+/// Checks the order of the children of documents and levels: `meta`
+/// first in a document, and `num`, `heading`, `subheading` leading a
+/// level in that order.
+fn check_order(tag: &Tag, diagnostics: &mut Vec<Diagnostic>) {
+    if is_document(&tag.tag_type) {
+        for child in tag.children.iter().skip(1) {
+            if child.tag_type == TagType::Core(Core::Meta) {
+                diagnostics.push(Diagnostic::error(
+                    "content-model",
+                    format!(
+                        "`meta` must be the first element of `{}`",
+                        tag.tag_type.name()
+                    ),
+                    child.span(),
+                ));
+            }
+        }
+    }
+    if !is_level(&tag.tag_type) {
+        return;
+    }
+    let slot = |child: &Tag| match child.tag_type {
+        TagType::Core(Core::Num) => 0,
+        TagType::Core(Core::Heading) => 1,
+        TagType::Core(Core::Subheading) => 2,
+        _ => 3,
+    };
+    let mut previous: Option<&Tag> = None;
+    for child in &tag.children {
+        if let Some(previous) = previous {
+            if slot(child) < slot(previous) {
+                diagnostics.push(Diagnostic::error(
+                    "content-model",
+                    format!(
+                        "`{}` must come before `{}` in `{}`",
+                        child.tag_type.name(),
+                        previous.tag_type.name(),
+                        tag.tag_type.name()
+                    ),
+                    child.span(),
+                ));
+            }
+        }
+        previous = Some(child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn messages(input: &str) -> Vec<String> {
+        let mut input = input;
+        let uslm = Uslm::parse(&mut input).unwrap();
+        uslm.validate()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_valid_document() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5">
+<meta><property name="docTitle">Title 5</property></meta>
+<main>
+<title><num value="5">Title 5.</num><heading>Government Organization</heading>
+<chapter><num value="5">CHAPTER 5.</num>
+<section><num value="552">Sec. 552.</num><heading>Public information</heading>
+<subsection><num value="a">a.</num><content><ref href="/us/usc/t5/s551">section 551</ref></content></subsection>
+<sourceCredit>Pub. L. 89–554</sourceCredit>
+<notes><note><heading>Amendments</heading></note></notes>
+</section>
+</chapter>
+</title>
+</main>
+</uscDoc>"#;
+
+        let output = messages(input);
+
+        assert_eq!(output, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_subsection_under_title() {
+        let input = r#"<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0"><main><title><subsection><num value="a">a.</num></subsection></title></main></uscDoc>"#;
+
+        let mut source = input;
+        let uslm = Uslm::parse(&mut source).unwrap();
+        let output = uslm.validate();
+
+        let start = input.find("<subsection>").unwrap();
+        let end = input.find("</title>").unwrap();
+        assert_eq!(
+            output,
+            vec![Diagnostic::error(
+                "content-model",
+                "`subsection` is not allowed in `title`",
+                Span::new(start, end)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_validate_num_after_heading() {
+        let input = r#"<bill xmlns="http://schemas.gpo.gov/xml/uslm"><main><section><heading>Short title</heading><num value="1">Sec. 1.</num></section></main></bill>"#;
+
+        let output = messages(input);
+
+        assert_eq!(
+            output,
+            vec!["`num` must come before `heading` in `section`"]
+        );
+    }
+
+    #[test]
+    fn test_validate_inverted_hierarchy() {
+        let input = r#"<bill xmlns="http://schemas.gpo.gov/xml/uslm"><main><section><subsection><subparagraph></subparagraph></subsection><paragraph><subparagraph><clause><content><paragraph></paragraph></content></clause></subparagraph></paragraph></section></main></bill>"#;
+
+        let mut source = input;
+        let uslm = Uslm::parse(&mut source).unwrap();
+        let output = uslm.validate();
+
+        let nested = input.find("<paragraph></paragraph>").unwrap();
+        assert_eq!(
+            output
+                .iter()
+                .map(|diagnostic| (diagnostic.code, diagnostic.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "content-model",
+                    "`subparagraph` is not allowed in `subsection`"
+                ),
+                (
+                    "inverted-hierarchy",
+                    "`paragraph` cannot be nested in `clause`"
+                ),
+            ]
+        );
+        assert_eq!(output[1].span.start, nested);
+    }
+
+    #[test]
+    fn test_validate_toc_item_outside_toc() {
+        let input = r#"<lawDoc xmlns="http://xml.house.gov/schemas/uslm/1.0"><main><layout><tocItem title="Chapter 1"></tocItem></layout></main></lawDoc>"#;
+
+        let output = messages(input);
+
+        assert_eq!(output, vec!["`tocItem` is not allowed in `layout`"]);
+    }
+
+    #[test]
+    fn test_validate_required_attributes() {
+        let input = r#"<lawDoc><meta><property>Title 5</property></meta><main><ref>section 552</ref></main></lawDoc>"#;
+
+        let output = messages(input);
+
+        assert_eq!(
+            output,
+            vec![
+                "`lawDoc` does not declare the USLM namespace",
+                "`property` is missing the required `name` attribute",
+                "`ref` is missing the required `href` or `idref` attribute",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_to_json() {
+        let source = "<lawDoc>\n<ref>section 552</ref>\n</lawDoc>";
+        let diagnostic = Diagnostic::error(
+            "required-attribute",
+            "`ref` is missing the \"href\" attribute",
+            Span::new(9, 31),
+        );
+
+        let output = diagnostic.to_json("title5.xml", &LineIndex::new(source));
+
+        assert_eq!(
+            output,
+            r#"{"file":"title5.xml","line":2,"column":1,"start":9,"end":31,"severity":"error","code":"required-attribute","message":"`ref` is missing the \"href\" attribute"}"#
+        );
+    }
+}
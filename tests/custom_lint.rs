@@ -0,0 +1,45 @@
+use pretty_assertions::assert_eq;
+use uslm_rs::{kind, Diagnostic, Level, Lint, Linter, Tag, TagType, Uslm};
+
+/// A rule defined outside the crate: sections must have an `id`.
+struct SectionId;
+
+impl Lint for SectionId {
+    fn code(&self) -> &'static str {
+        "section-id"
+    }
+
+    fn check(&self, tag: &Tag, _parent: Option<&Tag>, diagnostics: &mut Vec<Diagnostic>) {
+        if tag.tag_type == TagType::Level(Level::Section) && tag.attr::<kind::Id>().is_none() {
+            diagnostics.push(Diagnostic::warning(
+                self.code(),
+                "a `section` without an `id`".to_string(),
+                tag.span(),
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_custom_lint() {
+    let mut input = r#"<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5">
+<main>
+<section id="s1"><num value="1">§ 1.</num><heading>Short title</heading><content>Text.</content></section>
+<section><num value="2">§ 2.</num><heading>Definitions</heading><content>Text.</content></section>
+</main>
+</uscDoc>"#;
+    let uslm = Uslm::parse(&mut input).unwrap();
+    let mut linter = Linter::new();
+    linter.register(SectionId);
+
+    let output: Vec<_> = linter
+        .lint(&uslm)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.message))
+        .collect();
+
+    assert_eq!(
+        output,
+        vec![("section-id", "a `section` without an `id`".to_string())]
+    );
+}
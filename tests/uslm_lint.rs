@@ -0,0 +1,135 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+use pretty_assertions::assert_eq;
+
+const CLEAN: &str = r#"<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5">
+<main>
+<section id="s1" identifier="/us/usc/t5/s1"><num value="1">§ 1.</num><heading>Short title</heading><content>This title may be cited as title 5.</content></section>
+</main>
+</uscDoc>"#;
+
+const DANGLING: &str = r#"<uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5">
+<main>
+<section id="s1" identifier="/us/usc/t5/s1"><num value="1">§ 1.</num><heading>Short title</heading><content><ref idref="s2">section 2</ref></content></section>
+</main>
+</uscDoc>"#;
+
+/// Writes `contents` to `name` in the scratch directory of the tests.
+/// Every test uses its own names, as tests run in parallel.
+fn file(name: &str, contents: &str) -> String {
+    let path: PathBuf = [env!("CARGO_TARGET_TMPDIR"), name].iter().collect();
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn lint(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_uslm-lint"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_clean_document() {
+    let document = file("clean.xml", CLEAN);
+
+    let output = lint(&[&document]);
+
+    assert_eq!(stdout(&output), "");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_error_json() {
+    let document = file("dangling.xml", DANGLING);
+
+    let output = lint(&["--format", "json", &document]);
+
+    assert_eq!(
+        stdout(&output),
+        format!(
+            concat!(
+                r#"{{"file":"{}","line":3,"column":114,"start":200,"end":210,"#,
+                r#""severity":"error","code":"dangling-idref","#,
+                r#""message":"the `idref` `s2` names no `id` of the document"}}"#,
+                "\n"
+            ),
+            document
+        )
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_error_text() {
+    let document = file("dangling-text.xml", DANGLING);
+
+    let output = lint(&[&document]);
+
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{document}:3:114: error[dangling-idref]: the `idref` `s2` names no `id` of the document\n"
+        )
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_warnings_only() {
+    let document = file(
+        "warnings.xml",
+        &CLEAN.replace(r#" xmlns="http://xml.house.gov/schemas/uslm/1.0""#, ""),
+    );
+
+    let output = lint(&[&document]);
+
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{document}:1:1: warning[required-attribute]: `uscDoc` does not declare the USLM namespace\n"
+        )
+    );
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_unsupported_config() {
+    let document = file("unsupported.xml", CLEAN);
+    let config = file("unsupported.toml", "rules.missing-num.enabled = false\n");
+
+    let output = lint(&["--config", &config, &document]);
+
+    assert_eq!(
+        stderr(&output),
+        format!("uslm-lint: {config}: line 1: dotted key `rules.missing-num.enabled` is not supported, use a `[rules.<name>]` table\n")
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_unreadable_arguments() {
+    let missing = lint(&["no-such-file.xml"]);
+    let no_files = lint(&[]);
+    let unknown = lint(&["--color", "always"]);
+
+    assert_eq!(missing.status.code(), Some(2));
+    assert_eq!(no_files.status.code(), Some(2));
+    assert_eq!(unknown.status.code(), Some(2));
+    assert_eq!(
+        stderr(&no_files),
+        "uslm-lint: usage: uslm-lint [--config lint.toml] [--format text|json] FILE...\n"
+    );
+}